@@ -1,10 +1,61 @@
-#![feature(once_cell)]
-
 use std::{collections::HashMap, sync::LazyLock};
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use fnv::FnvHashMap;
-use vfhm::builder::VfhmBuilder;
+use vfhm::{
+  builder::VfhmBuilder,
+  scheme::{HashScheme, Hashed},
+  soa::SoaVfhm,
+};
+
+const KEYWORDS: &[&str] = &[
+  "await",
+  "break",
+  "case",
+  "catch",
+  "class",
+  "const",
+  "continue",
+  "debugger",
+  "default",
+  "delete",
+  "do",
+  "else",
+  "enum",
+  "export",
+  "extends",
+  "false",
+  "finally",
+  "for",
+  "function",
+  "if",
+  "implements",
+  "import",
+  "in",
+  "instanceof",
+  "interface",
+  "let",
+  "new",
+  "null",
+  "package",
+  "private",
+  "protected",
+  "public",
+  "return",
+  "super",
+  "switch",
+  "static",
+  "this",
+  "throw",
+  "try",
+  "true",
+  "typeof",
+  "var",
+  "void",
+  "while",
+  "with",
+  "yield",
+];
 
 static PHF_KEYWORDS: phf::Map<&'static str, i32> = phf::phf_map! {
   "await" => 1,
@@ -55,6 +106,57 @@ static PHF_KEYWORDS: phf::Map<&'static str, i32> = phf::phf_map! {
   "yield" => 46,
 };
 
+vfhm_derive::perfect_map! {
+  PerfectKeywords<&'static str, i32> {
+    "await" => 1,
+    "break" => 2,
+    "case" => 3,
+    "catch" => 4,
+    "class" => 5,
+    "const" => 6,
+    "continue" => 7,
+    "debugger" => 8,
+    "default" => 9,
+    "delete" => 10,
+    "do" => 11,
+    "else" => 12,
+    "enum" => 13,
+    "export" => 14,
+    "extends" => 15,
+    "false" => 16,
+    "finally" => 17,
+    "for" => 18,
+    "function" => 19,
+    "if" => 20,
+    "implements" => 21,
+    "import" => 22,
+    "in" => 23,
+    "instanceof" => 24,
+    "interface" => 25,
+    "let" => 26,
+    "new" => 27,
+    "null" => 28,
+    "package" => 29,
+    "private" => 30,
+    "protected" => 31,
+    "public" => 32,
+    "return" => 33,
+    "super" => 34,
+    "switch" => 35,
+    "static" => 36,
+    "this" => 37,
+    "throw" => 38,
+    "try" => 39,
+    "true" => 40,
+    "typeof" => 41,
+    "var" => 42,
+    "void" => 43,
+    "while" => 44,
+    "with" => 45,
+    "yield" => 46,
+  }
+}
+
 macro_rules! add_keywords {
   ($ident:ident) => {
     $ident.insert("await", 1);
@@ -193,54 +295,7 @@ fn bench_vfhm(c: &mut Criterion) {
   println!("{}", black_box(TEXT_VALUES.len()));
 
   let mut hashmap = VfhmBuilder::default()
-    .set_keys(vec![
-      "await",
-      "break",
-      "case",
-      "catch",
-      "class",
-      "const",
-      "continue",
-      "debugger",
-      "default",
-      "delete",
-      "do",
-      "else",
-      "enum",
-      "export",
-      "extends",
-      "false",
-      "finally",
-      "for",
-      "function",
-      "if",
-      "implements",
-      "import",
-      "in",
-      "instanceof",
-      "interface",
-      "let",
-      "new",
-      "null",
-      "package",
-      "private",
-      "protected",
-      "public",
-      "return",
-      "super",
-      "switch",
-      "static",
-      "this",
-      "throw",
-      "try",
-      "true",
-      "typeof",
-      "var",
-      "void",
-      "while",
-      "with",
-      "yield",
-    ])
+    .set_keys(KEYWORDS.to_vec())
     .find_params(1_000_000)
     .build();
 
@@ -265,5 +320,174 @@ fn bench_vfhm(c: &mut Criterion) {
   );
 }
 
-criterion_group!(benches, bench_hashmap, bench_fnv, bench_phf, bench_vfhm);
+fn bench_vfhm_perfect(c: &mut Criterion) {
+  black_box(TEXT_VALUES.len());
+
+  let hashmap = PerfectKeywords::new();
+
+  c.bench_with_input(
+    BenchmarkId::new("vfhm_perfect", "jquery"),
+    &hashmap,
+    |b, hashmap| {
+      b.iter(|| {
+        let hashmap = black_box(hashmap);
+
+        TEXT_VALUES.iter().for_each(|(word, result)| {
+          assert_eq!(
+            hashmap.get(word.as_str()),
+            result.as_ref(),
+            "Failed on word {word}"
+          );
+        });
+      });
+    },
+  );
+}
+
+fn bench_vfhm_soa(c: &mut Criterion) {
+  black_box(TEXT_VALUES.len());
+
+  let mut builder = VfhmBuilder::<_, i32>::default();
+  builder.set_keys(KEYWORDS.to_vec()).find_params(1_000_000);
+
+  let mut hashmap = SoaVfhm::with_params(builder.into_params());
+
+  add_keywords!(hashmap);
+
+  c.bench_with_input(
+    BenchmarkId::new("vfhm_soa", "jquery"),
+    &hashmap,
+    |b, hashmap| {
+      b.iter(|| {
+        let hashmap = black_box(hashmap);
+
+        TEXT_VALUES.iter().for_each(|(word, result)| {
+          assert_eq!(
+            hashmap.get(word.as_str()),
+            result.as_ref(),
+            "Failed on word {word}"
+          );
+        });
+      });
+    },
+  );
+}
+
+fn bench_vfhm_unchecked(c: &mut Criterion) {
+  black_box(TEXT_VALUES.len());
+
+  let mut hashmap = VfhmBuilder::default()
+    .set_keys(KEYWORDS.to_vec())
+    .find_params(1_000_000)
+    .build();
+
+  add_keywords!(hashmap);
+
+  c.bench_with_input(
+    BenchmarkId::new("vfhm_unchecked", "jquery"),
+    &hashmap,
+    |b, hashmap| {
+      b.iter(|| {
+        let hashmap = black_box(hashmap);
+
+        TEXT_VALUES.iter().for_each(|(word, result)| {
+          assert_eq!(
+            hashmap.get_unchecked(word.as_str()),
+            result.as_ref(),
+            "Failed on word {word}"
+          );
+        });
+      });
+    },
+  );
+}
+
+fn bench_vfhm_routed(c: &mut Criterion) {
+  black_box(TEXT_VALUES.len());
+
+  let mut hashmap = VfhmBuilder::default()
+    .set_keys(KEYWORDS.to_vec())
+    .find_params(1_000_000)
+    .build();
+
+  add_keywords!(hashmap);
+
+  let hashmap = hashmap.route_first_byte();
+
+  c.bench_with_input(
+    BenchmarkId::new("vfhm_routed", "jquery"),
+    &hashmap,
+    |b, hashmap| {
+      b.iter(|| {
+        let hashmap = black_box(hashmap);
+
+        TEXT_VALUES.iter().for_each(|(word, result)| {
+          assert_eq!(
+            hashmap.get(word.as_str()),
+            result.as_ref(),
+            "Failed on word {word}"
+          );
+        });
+      });
+    },
+  );
+}
+
+/// `Multiplicative` without the paired mixing, one byte per loop trip.
+struct Scalar;
+
+impl HashScheme for Scalar {
+  #[inline]
+  fn mix(index: usize, byte: u8, seed: usize) -> usize {
+    index.wrapping_mul(byte as usize).wrapping_sub(seed)
+  }
+}
+
+fn bench_vfhm_scalar(c: &mut Criterion) {
+  black_box(TEXT_VALUES.len());
+
+  let mut builder = VfhmBuilder::default();
+  builder
+    .set_entries(
+      KEYWORDS
+        .iter()
+        .map(|keyword| Hashed::<_, Scalar>::new(*keyword))
+        .zip(1..)
+        .collect(),
+    )
+    .find_params(1_000_000);
+
+  let hashmap = builder.build_populated();
+
+  c.bench_with_input(
+    BenchmarkId::new("vfhm_scalar", "jquery"),
+    &hashmap,
+    |b, hashmap| {
+      b.iter(|| {
+        let hashmap = black_box(hashmap);
+
+        TEXT_VALUES.iter().for_each(|(word, result)| {
+          assert_eq!(
+            hashmap.get(&Hashed::new(word.as_str())),
+            result.as_ref(),
+            "Failed on word {word}"
+          );
+        });
+      });
+    },
+  );
+}
+
+criterion_group!(
+  benches,
+  bench_hashmap,
+  bench_fnv,
+  bench_phf,
+  bench_vfhm,
+  bench_vfhm_unchecked,
+  bench_vfhm_routed,
+  bench_vfhm_scalar,
+  bench_vfhm_perfect,
+  bench_vfhm_soa
+);
 criterion_main!(benches);
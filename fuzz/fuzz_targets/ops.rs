@@ -0,0 +1,69 @@
+//! Applies random operations to a `Vfhm` and a `HashMap` side by side. Keys come from a tiny
+//! alphabet and the table has 8 slots, so most inserts collide with another key.
+
+#![no_main]
+
+use std::collections::HashMap;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use vfhm::{Vfhm, VfhmParams};
+
+const ALPHABET: &[u8] = b"ab\0";
+
+#[derive(Debug)]
+struct Key(Vec<u8>);
+
+impl<'a> Arbitrary<'a> for Key {
+  fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+    let len = u.int_in_range(0..=3)?;
+
+    (0..len)
+      .map(|_| u.choose(ALPHABET).copied())
+      .collect::<arbitrary::Result<_>>()
+      .map(Key)
+  }
+}
+
+#[derive(Debug, Arbitrary)]
+enum Op {
+  Insert(Key, u8),
+  Remove(Key),
+  Get(Key),
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+  seed: usize,
+  ops: Vec<Op>,
+}
+
+fuzz_target!(|input: Input| {
+  let mut hashmap = Vfhm::with_params(VfhmParams(input.seed, 0b111, 0, (0, usize::MAX)));
+  let mut reference = HashMap::new();
+
+  for op in input.ops {
+    match op {
+      Op::Insert(Key(key), value) => {
+        // A key sharing the slot is evicted, mirror that in the reference.
+        if let Some((evicted, old)) = hashmap.insert(key.clone(), value) {
+          assert_eq!(reference.remove(&evicted), Some(old));
+        }
+
+        reference.insert(key, value);
+      }
+      Op::Remove(Key(key)) => {
+        assert_eq!(hashmap.remove(key.as_slice()), reference.remove(&key));
+      }
+      Op::Get(Key(key)) => {
+        assert_eq!(hashmap.get(key.as_slice()), reference.get(&key));
+      }
+    }
+
+    assert_eq!(hashmap.len(), reference.len());
+
+    for (key, value) in &reference {
+      assert_eq!(hashmap.get(key.as_slice()), Some(value));
+    }
+  }
+});
@@ -0,0 +1,121 @@
+use core::borrow::Borrow;
+
+use rkyv::Archive;
+
+use crate::{ArchivedVfhm, ArchivedVfhmParams, VfhmKey, VfhmParams};
+
+impl From<&ArchivedVfhmParams> for VfhmParams {
+  fn from(params: &ArchivedVfhmParams) -> Self {
+    VfhmParams(
+      params.0.to_native() as usize,
+      params.1.to_native() as usize,
+      params.2.to_native() as usize,
+      (
+        params.3 .0.to_native() as usize,
+        params.3 .1.to_native() as usize,
+      ),
+    )
+  }
+}
+
+/// Lookups run directly on the archived table, which keeps the slot layout of the original map.
+/// Keys are hashed natively, so the archive has to be read on a target with the same pointer width
+/// it was written on.
+impl<K, V> ArchivedVfhm<K, V>
+where
+  K: Archive,
+  V: Archive,
+{
+  pub fn len(&self) -> usize {
+    self.length.to_native() as usize
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K::Archived: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  pub fn get<Q>(&self, key: &Q) -> Option<&V::Archived>
+  where
+    K::Archived: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    let params = VfhmParams::from(&self.params);
+
+    // The params come from untrusted bytes, so they may not match the table they sit next to.
+    if !params.is_valid() || !params.bound_check(key) {
+      return None;
+    }
+
+    self
+      .table
+      .get(key.table_key(params))?
+      .as_ref()
+      .filter(|entry| key.table_key_compare(entry.0.borrow()))
+      .map(|entry| &entry.1)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rkyv::rancor::Error;
+
+  use crate::{builder::VfhmBuilder, ArchivedVfhm, VfhmParams};
+
+  #[test]
+  fn archived_lookup() {
+    let days = ["sunday", "monday", "tuesday", "wednesday"];
+
+    let mut builder = VfhmBuilder::default();
+    builder
+      .set_entries(days.iter().map(|day| day.to_string()).zip(1..).collect())
+      .find_params(1000);
+
+    let hashmap = builder.build_populated();
+    let bytes = rkyv::to_bytes::<Error>(&hashmap).unwrap();
+    let archived = rkyv::access::<ArchivedVfhm<String, i32>, Error>(&bytes).unwrap();
+
+    assert_eq!(archived.len(), 4);
+    assert_eq!(VfhmParams::from(&archived.params), hashmap.params);
+
+    for (value, day) in (1..).zip(days) {
+      assert_eq!(archived.get(day), Some(&value.into()));
+    }
+
+    assert_eq!(archived.get("friday"), None);
+
+    let restored = rkyv::deserialize::<_, Error>(archived).unwrap();
+
+    assert_eq!(hashmap, restored);
+  }
+
+  #[test]
+  fn archived_mismatched_table() {
+    let mut hashmap = VfhmBuilder::default()
+      .set_entries(vec![("sunday".to_string(), 1)])
+      .build_populated();
+
+    hashmap.params = VfhmParams(0, 255, 0, (0, 10));
+
+    let bytes = rkyv::to_bytes::<Error>(&hashmap).unwrap();
+    let archived = rkyv::access::<ArchivedVfhm<String, i32>, Error>(&bytes).unwrap();
+
+    for key in ["monday", "tuesday", "wednesday", "thursday", "friday"] {
+      assert_eq!(archived.get(key), None);
+    }
+
+    hashmap.params = VfhmParams(0, 1, usize::BITS as usize, (0, 10));
+
+    let bytes = rkyv::to_bytes::<Error>(&hashmap).unwrap();
+    let archived = rkyv::access::<ArchivedVfhm<String, i32>, Error>(&bytes).unwrap();
+
+    assert_eq!(archived.get("sunday"), None);
+  }
+}
@@ -0,0 +1,111 @@
+use core::{array, borrow::Borrow, mem};
+
+use crate::{VfhmKey, VfhmParams};
+
+#[derive(Debug, Clone)]
+pub struct ArrayVfhm<K, V, const N: usize> {
+  table: [Option<(K, V)>; N],
+  params: VfhmParams,
+  length: usize,
+}
+
+impl<K, V, const N: usize> ArrayVfhm<K, V, N> {
+  pub fn with_params<P>(maybe_params: P) -> Self
+  where
+    P: Into<VfhmParams>,
+  {
+    let params = maybe_params.into();
+
+    assert_eq!(
+      N,
+      params.mask_size(),
+      "table size must match the mask size of the params"
+    );
+
+    ArrayVfhm {
+      table: array::from_fn(|_| None),
+      params,
+      length: 0,
+    }
+  }
+}
+
+impl<K, V, const N: usize> ArrayVfhm<K, V, N>
+where
+  K: VfhmKey,
+{
+  pub fn len(&self) -> usize {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    self.table[key.table_key(self.params)]
+      .iter()
+      .find(|(k, _)| key.table_key_compare(k.borrow()))
+      .map(|(_, value)| value)
+  }
+
+  pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+    let index = key.table_key(self.params);
+
+    let output = self.table[index].replace((key, value));
+
+    if output.is_none() {
+      self.length += 1;
+    }
+
+    output
+  }
+
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.remove_entry(key).map(|(_, value)| value)
+  }
+
+  /// Like [`ArrayVfhm::remove`] but also hands back the stored key.
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    let slot = &mut self.table[key.table_key(self.params)];
+
+    if !slot
+      .as_ref()
+      .is_some_and(|(k, _)| key.table_key_compare(k.borrow()))
+    {
+      return None;
+    }
+
+    self.length -= 1;
+
+    mem::take(slot)
+  }
+}
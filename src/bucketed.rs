@@ -0,0 +1,162 @@
+use alloc::vec::Vec;
+use core::{borrow::Borrow, mem};
+
+use smallvec::SmallVec;
+
+use crate::{VfhmKey, VfhmParams};
+
+type Bucket<K, V> = SmallVec<[(K, V); 2]>;
+
+/// A `Vfhm` where every slot holds a small bucket, so params that leave a few colliding keys are
+/// still usable at the cost of scanning the bucket on lookup.
+#[derive(Debug, Clone)]
+pub struct BucketedVfhm<K, V> {
+  table: Vec<Bucket<K, V>>,
+  params: VfhmParams,
+  length: usize,
+}
+
+impl<K, V> BucketedVfhm<K, V> {
+  pub fn with_params<P>(maybe_params: P) -> Self
+  where
+    P: Into<VfhmParams>,
+  {
+    let params = maybe_params.into();
+
+    BucketedVfhm {
+      table: (0..params.mask_size()).map(|_| SmallVec::new()).collect(),
+      params,
+      length: 0,
+    }
+  }
+}
+
+impl<K, V> BucketedVfhm<K, V>
+where
+  K: VfhmKey,
+{
+  pub fn len(&self) -> usize {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    self.table[key.table_key(self.params)]
+      .iter()
+      .find(|(k, _)| key.table_key_compare(k.borrow()))
+      .map(|(_, value)| value)
+  }
+
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    self.table[key.table_key(self.params)]
+      .iter_mut()
+      .find(|(k, _)| key.table_key_compare(k.borrow()))
+      .map(|(_, value)| value)
+  }
+
+  pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+    let bucket = &mut self.table[key.table_key(self.params)];
+
+    match bucket.iter_mut().find(|(k, _)| key.table_key_compare(k)) {
+      Some(entry) => Some(mem::replace(entry, (key, value))),
+      None => {
+        bucket.push((key, value));
+        self.length += 1;
+
+        None
+      }
+    }
+  }
+
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.remove_entry(key).map(|(_, value)| value)
+  }
+
+  /// Like [`BucketedVfhm::remove`] but also hands back the stored key.
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    let bucket = &mut self.table[key.table_key(self.params)];
+    let position = bucket
+      .iter()
+      .position(|(k, _)| key.table_key_compare(k.borrow()))?;
+
+    self.length -= 1;
+
+    Some(bucket.swap_remove(position))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::BucketedVfhm;
+  use crate::{builder::VfhmBuilder, VfhmParams};
+
+  #[test]
+  fn colliding_keys() {
+    // Every seed maps these keys to the same slot.
+    let keys = ["a\0", "b\0", "c\0"];
+
+    assert!(VfhmBuilder::<_, ()>::default()
+      .set_keys(keys.to_vec())
+      .try_find_params(1000)
+      .is_err());
+
+    let mut hashmap = BucketedVfhm::with_params(VfhmParams(0, 3, 0, (2, 2)));
+
+    for (value, key) in (1..).zip(keys) {
+      assert_eq!(hashmap.insert(key, value), None);
+    }
+
+    assert_eq!(hashmap.len(), 3);
+    assert_eq!(hashmap.get("a\0"), Some(&1));
+    assert_eq!(hashmap.get("b\0"), Some(&2));
+    assert_eq!(hashmap.get("c\0"), Some(&3));
+    assert_eq!(hashmap.get("d\0"), None);
+
+    assert_eq!(hashmap.remove("a\0"), Some(1));
+    assert_eq!(hashmap.get("c\0"), Some(&3));
+    assert_eq!(hashmap.insert("b\0", 4), Some(("b\0", 2)));
+    assert_eq!(hashmap.len(), 2);
+
+    assert_eq!(hashmap.remove_entry("b\0"), Some(("b\0", 4)));
+    assert_eq!(hashmap.len(), 1);
+  }
+}
@@ -1,75 +1,303 @@
-use std::{marker::PhantomData, mem};
+use alloc::{format, string::String, vec, vec::Vec};
+use core::mem;
 
-use crate::{Vfhm, VfhmParams};
+pub use crate::error::CollisionReport;
+use crate::{set::VfhmSet, Vfhm, VfhmError, VfhmKey, VfhmParams};
+
+const DEFAULT_MAX_ITERATIONS: usize = 1_000_000;
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 4096;
 
 #[derive(Debug)]
 pub struct VfhmBuilder<K, V> {
   keys: Vec<K>,
+  values: Vec<V>,
   params: VfhmParams,
-  _values: PhantomData<V>,
+  search_seed: usize,
+}
+
+impl<K, V> VfhmBuilder<K, V> {
+  /// Starts the search from the mask [`VfhmParams::with_capacity`] picks instead of the smallest
+  /// one, for key sets that need a sparse table anyway.
+  pub fn with_capacity(capacity: usize) -> Self {
+    let VfhmParams(_, mask, mask_offset, _) = VfhmParams::with_capacity(capacity);
+    let mut builder = VfhmBuilder {
+      keys: Vec::with_capacity(capacity),
+      ..VfhmBuilder::default()
+    };
+
+    builder.params.set_mask(mask);
+    builder.params.set_mask_offset(mask_offset);
+
+    builder
+  }
 }
 
 impl<K, V> VfhmBuilder<K, V>
 where
-  K: AsRef<[u8]>,
+  K: VfhmKey,
 {
-  pub fn set_keys(&mut self, keys: Vec<K>) -> &mut Self {
-    let (lower, upper) = self.params.bounds_mut();
+  pub fn set_keys<I>(&mut self, keys: I) -> &mut Self
+  where
+    I: IntoIterator<Item = K>,
+  {
+    let keys: Vec<K> = keys.into_iter().collect();
 
     for key in &keys {
-      let len = key.as_ref().len();
-      *lower = len.min(*lower);
-      *upper = len.max(*upper);
+      self.extend_bounds(key);
+    }
+
+    self.keys = keys;
+    self.values.clear();
+
+    self
+  }
+
+  pub fn add_key(&mut self, key: K) -> &mut Self {
+    self.extend_bounds(&key);
+    self.keys.push(key);
+
+    self
+  }
+
+  /// Widens the bounds derived from the keys to cover `bounds`, they never shrink below the keys.
+  /// Inverted bounds are kept as given so `try_build` reports them.
+  pub fn set_bounds(&mut self, bounds: (usize, usize)) -> &mut Self {
+    self.params.set_bounds(bounds);
+
+    if bounds.0 > bounds.1 {
+      return self;
     }
 
+    let keys = core::mem::take(&mut self.keys);
+    for key in &keys {
+      self.extend_bounds(key);
+    }
     self.keys = keys;
 
     self
   }
 
+  fn extend_bounds(&mut self, key: &K) {
+    let (lower, upper) = self.params.bounds_mut();
+
+    let len = key.key_len();
+    *lower = len.min(*lower);
+    *upper = len.max(*upper);
+  }
+
+  pub fn set_entries(&mut self, entries: Vec<(K, V)>) -> &mut Self {
+    let (keys, values): (Vec<K>, Vec<V>) = entries.into_iter().unzip();
+
+    self.set_keys(keys);
+    self.values = values;
+
+    self
+  }
+
+  /// Shifts the seeds tried for every mask to start at `seed`. The candidates keep the canonical
+  /// order of [`VfhmBuilder::try_find_params`], so the same keys and search seed always give the
+  /// same params.
+  pub fn search_seed(&mut self, seed: usize) -> &mut Self {
+    self.params.set_seed(seed);
+    self.search_seed = seed;
+
+    self
+  }
+
   pub fn find_params(&mut self, max_iterations: usize) -> &mut Self {
+    self.try_find_params(max_iterations).unwrap()
+  }
+
+  /// Checks candidates in a canonical order and keeps the first conflictless one, so the same keys
+  /// and [search seed](VfhmBuilder::search_seed) always give the same params. Starting from the
+  /// narrowest mask with a slot per key, every seed from the search seed up is tried for a mask,
+  /// then the mask is slid one bit up, and once it can't slide it's widened by one bit from the
+  /// low bits.
+  pub fn try_find_params(&mut self, max_iterations: usize) -> Result<&mut Self, VfhmError> {
+    self.params = search(&self.keys, self.params, self.search_seed, |iterations| {
+      iterations < max_iterations
+    })?;
+
+    Ok(self)
+  }
+
+  /// Like [`VfhmBuilder::try_find_params`] but bounded by wall-clock time instead of an iteration
+  /// count.
+  #[cfg(feature = "std")]
+  pub fn find_params_timed(&mut self, budget: std::time::Duration) -> Result<&mut Self, VfhmError> {
+    let start = std::time::Instant::now();
+
+    self.params = search(&self.keys, self.params, self.search_seed, |_| {
+      start.elapsed() < budget
+    })?;
+
+    Ok(self)
+  }
+
+  pub fn find_params_min_size(&mut self, max_iterations: usize) -> &mut Self {
+    self.try_find_params_min_size(max_iterations).unwrap()
+  }
+
+  /// Runs the first-fit search, then spends what's left of the budget on seeds it never reaches
+  /// at each smaller [`VfhmParams::mask_size`], smallest first, keeping the first conflictless
+  /// params found.
+  pub fn try_find_params_min_size(
+    &mut self,
+    max_iterations: usize,
+  ) -> Result<&mut Self, VfhmError> {
+    let mut checked = 0;
+    let first_fit = search(&self.keys, self.params, self.search_seed, |iterations| {
+      checked = iterations;
+      iterations < max_iterations
+    })?;
+
+    let VfhmParams(_, _, _, bounds) = first_fit;
+    let widths =
+      self.keys.len().next_power_of_two().trailing_zeros()..first_fit.mask_size().trailing_zeros();
+    let mut remaining = max_iterations - checked;
+
+    for (index, width) in widths.clone().enumerate() {
+      let budget = remaining / (widths.len() - index);
+      remaining -= budget;
+
+      // The first-fit search already went through one seed per slot at this width.
+      let first_seed = self.search_seed.wrapping_add(1 << width);
+      let candidates = (0..)
+        .map(|seed| first_seed.wrapping_add(seed))
+        .flat_map(|seed| {
+          (0..=usize::BITS - width).map(move |offset| {
+            VfhmParams(seed, ((1 << width) - 1) << offset, offset as usize, bounds)
+          })
+        })
+        .take(budget);
+
+      for candidate in candidates {
+        if collisions(&self.keys, candidate) == 0 {
+          self.params = candidate;
+
+          return Ok(self);
+        }
+      }
+    }
+
+    self.params = first_fit;
+
+    Ok(self)
+  }
+
+  /// Settles for the params with the fewest collisions when no conflictless ones are found,
+  /// removing the keys that collide with an earlier key and returning them so they can be kept in
+  /// a fallback list. Values set for the removed keys are dropped, the bounds still cover them.
+  pub fn find_params_or_overflow(&mut self, max_iterations: usize) -> (VfhmParams, Vec<K>) {
+    let mut best = (usize::MAX, self.params);
+
     for _ in 0..max_iterations {
-      let mut map = Vfhm::with_params(self.params);
+      let collisions = collisions(&self.keys, self.params);
+
+      if collisions < best.0 {
+        best = (collisions, self.params);
+      }
 
-      for (index, key) in self.keys.iter().enumerate() {
-        map.insert(key, index);
+      if collisions == 0 {
+        break;
       }
 
-      if self
-        .keys
-        .iter()
-        .enumerate()
-        .all(|(index, key)| map.get(key) == Some(&index))
-      {
-        return self;
+      match self.params.next_candidate(self.search_seed) {
+        Some(next) => self.params = next,
+        None => break,
       }
+    }
 
-      let VfhmParams(mut seed, mut mask, mut mask_offset, bounds) = self.params;
+    self.params = best.1;
 
-      if seed == mask >> mask_offset {
-        seed = 0;
+    let mut occupied = vec![false; self.params.mask_size()];
+    let placed: Vec<bool> = self
+      .keys
+      .iter()
+      .map(|key| !mem::replace(&mut occupied[key.table_key(self.params)], true))
+      .collect();
 
-        if mask_offset + ((1.0 + (mask >> mask_offset) as f64).sqrt() as usize) + 1
-          < mem::size_of::<usize>() * 8
-        {
-          mask <<= 1;
-          mask_offset += 1;
-        } else {
-          mask = (mask >> (mask_offset - 1)) | 1;
-          mask_offset = 0;
-        }
+    let mut overflow = Vec::new();
+
+    for (key, placed) in mem::take(&mut self.keys).into_iter().zip(&placed) {
+      if *placed {
+        self.keys.push(key);
       } else {
-        seed += 1;
+        overflow.push(key);
       }
-
-      self.params = VfhmParams(seed, mask, mask_offset, bounds);
     }
 
-    panic!("Max Interations passed no conflictless key found")
+    let mut placed = placed.into_iter();
+    self.values.retain(|_| placed.next().unwrap_or(false));
+
+    (self.params, overflow)
+  }
+
+  pub fn collision_report(&self, params: VfhmParams) -> CollisionReport {
+    collision_report(&self.keys, params)
   }
 
+  /// The load the built map will have once every key is inserted.
+  pub fn load_factor(&self) -> f64 {
+    self.keys.len() as f64 / self.params.mask_size() as f64
+  }
+
+  /// Panics when the bounds were set inverted, see [`VfhmBuilder::try_build`].
   pub fn build(&self) -> Vfhm<K, V> {
-    Vfhm::with_params(self.params)
+    self.try_build().unwrap()
+  }
+
+  /// Without any keys the bounds are normalized to `(0, 0)`, so the map only accepts the empty
+  /// key.
+  pub fn try_build(&self) -> Result<Vfhm<K, V>, VfhmError> {
+    let mut params = self.params;
+    let (lower, upper) = params.bounds_mut();
+
+    if lower > upper {
+      if !self.keys.is_empty() {
+        return Err(VfhmError::InvalidBounds {
+          lower: *lower,
+          upper: *upper,
+        });
+      }
+
+      (*lower, *upper) = (0, 0);
+    }
+
+    Ok(Vfhm::with_params(params))
+  }
+
+  /// Builds like [`VfhmBuilder::build`] and inserts the entries, leaving the builder empty.
+  pub fn build_populated(&mut self) -> Vfhm<K, V> {
+    let mut map = self.build();
+
+    for (key, value) in mem::take(&mut self.keys)
+      .into_iter()
+      .zip(mem::take(&mut self.values))
+    {
+      map.insert(key, value);
+    }
+
+    map
+  }
+
+  pub fn emit_static(&self, type_name: &str) -> String {
+    let VfhmParams(seed, mask, mask_offset, (lower, upper)) = self.params;
+
+    format!(
+      "pub struct {type_name};
+
+impl vfhm::r#static::VfhmStaticMap for {type_name} {{
+  const SEED: usize = {seed};
+  const MASK: usize = {mask};
+  const MASK_OFFSET: usize = {mask_offset};
+  const BONDS: (usize, usize) = ({lower}, {upper});
+}}
+
+pub type {type_name}Map<K, V> = vfhm::r#static::StaticVfhm<K, V, {type_name}>;
+"
+    )
   }
 
   pub fn into_params(self) -> VfhmParams {
@@ -77,12 +305,870 @@ where
   }
 }
 
+impl<K> VfhmBuilder<K, ()>
+where
+  K: VfhmKey,
+{
+  /// Builds a set holding every key, the params are normalized like [`VfhmBuilder::build`].
+  pub fn build_set(&mut self) -> VfhmSet<K> {
+    let mut set = VfhmSet::with_params(self.build().params);
+
+    for key in mem::take(&mut self.keys) {
+      set.insert(key);
+    }
+
+    set
+  }
+}
+
+/// Finds params separating `keys` without a builder, searching the same candidates as
+/// [`VfhmBuilder::try_find_params`] with bounds fitted to the keys.
+pub fn search_params<K>(keys: &[K], max_iterations: usize) -> Result<VfhmParams, VfhmError>
+where
+  K: VfhmKey,
+{
+  let bounds = keys.iter().fold((usize::MAX, 0), |(lower, upper), key| {
+    (lower.min(key.key_len()), upper.max(key.key_len()))
+  });
+
+  search(keys, VfhmParams(0, 1, 0, bounds), 0, |iterations| {
+    iterations < max_iterations
+  })
+}
+
+/// Walks the candidates while `proceed` accepts the number of candidates checked so far.
+fn search<K, F>(
+  keys: &[K],
+  mut params: VfhmParams,
+  search_seed: usize,
+  mut proceed: F,
+) -> Result<VfhmParams, VfhmError>
+where
+  K: VfhmKey,
+  F: FnMut(usize) -> bool,
+{
+  if keys.is_empty() {
+    return Err(VfhmError::NoKeys);
+  }
+
+  params = skip_undersized(keys.len(), params, search_seed)?;
+
+  let mut best = (usize::MAX, params);
+  let mut iterations = 0;
+
+  while proceed(iterations) {
+    let collisions = collisions(keys, params);
+
+    if collisions == 0 {
+      return Ok(params);
+    }
+
+    if collisions < best.0 {
+      best = (collisions, params);
+    }
+
+    params = params
+      .next_candidate(search_seed)
+      .ok_or(VfhmError::MaskOverflow)?;
+    iterations += 1;
+  }
+
+  Err(search_exhausted(keys, iterations, best))
+}
+
+/// Keeps the first occurrence of every key with the value of the last, like repeated
+/// `HashMap::insert` calls. Sorting by the unmasked hash brings repeats of a key together, so keys
+/// are only compared within runs of equal hashes.
+fn dedup_entries<K, V>(entries: Vec<(K, V)>) -> Vec<(K, V)>
+where
+  K: VfhmKey,
+{
+  let unmasked = VfhmParams(0, usize::MAX, 0, (0, usize::MAX));
+  let hashes: Vec<usize> = entries
+    .iter()
+    .map(|(key, _)| key.table_key(unmasked))
+    .collect();
+
+  let mut order: Vec<usize> = (0..entries.len()).collect();
+  order.sort_by_key(|index| hashes[*index]);
+
+  let mut entries: Vec<Option<(K, V)>> = entries.into_iter().map(Some).collect();
+
+  for run in order.chunk_by(|a, b| hashes[*a] == hashes[*b]) {
+    for (position, index) in run.iter().enumerate() {
+      let first = run[..position].iter().find(|earlier| {
+        matches!(
+          (&entries[**earlier], &entries[*index]),
+          (Some((first, _)), Some((key, _))) if key.table_key_compare(first)
+        )
+      });
+
+      let Some(first) = first else {
+        continue;
+      };
+
+      if let (Some((_, value)), Some(entry)) = (entries[*index].take(), &mut entries[*first]) {
+        entry.1 = value;
+      }
+    }
+  }
+
+  entries.into_iter().flatten().collect()
+}
+
+/// Masks with fewer slots than keys can't separate them, so this jumps to where the canonical
+/// order reaches the first mask that's wide enough.
+fn skip_undersized(
+  keys: usize,
+  params: VfhmParams,
+  search_seed: usize,
+) -> Result<VfhmParams, VfhmError> {
+  if params.mask_size() >= keys {
+    return Ok(params);
+  }
+
+  match keys.checked_next_power_of_two() {
+    Some(mask_size) if mask_size <= VfhmParams::MAX_MASK_SIZE => {
+      Ok(VfhmParams(search_seed, mask_size - 1, 0, params.bounds()))
+    }
+    _ => Err(VfhmError::MaskOverflow),
+  }
+}
+
+fn search_exhausted<K>(
+  keys: &[K],
+  iterations: usize,
+  (best_collisions, best_params): (usize, VfhmParams),
+) -> VfhmError
+where
+  K: VfhmKey,
+{
+  VfhmError::SearchExhausted {
+    iterations,
+    best_collisions,
+    best_report: collision_report(keys, best_params),
+  }
+}
+
+fn collision_report<K>(keys: &[K], params: VfhmParams) -> CollisionReport
+where
+  K: VfhmKey,
+{
+  let mut slots = vec![Vec::new(); params.mask_size()];
+
+  for (index, key) in keys.iter().enumerate() {
+    slots[key.table_key(params)].push(index);
+  }
+
+  slots
+    .into_iter()
+    .enumerate()
+    .filter(|(_, keys)| !keys.is_empty())
+    .collect()
+}
+
+fn collisions<K>(keys: &[K], params: VfhmParams) -> usize
+where
+  K: VfhmKey,
+{
+  // Clearing a table much larger than the key set costs more than sorting the slots.
+  if params.mask_size() / 8 > keys.len() {
+    let mut slots: Vec<usize> = keys.iter().map(|key| key.table_key(params)).collect();
+    slots.sort_unstable();
+
+    return slots.windows(2).filter(|pair| pair[0] == pair[1]).count();
+  }
+
+  let mut occupied = vec![false; params.mask_size()];
+
+  keys
+    .iter()
+    .filter(|key| mem::replace(&mut occupied[key.table_key(params)], true))
+    .count()
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V> VfhmBuilder<K, V>
+where
+  K: VfhmKey + Sync,
+{
+  pub fn find_params_parallel(&mut self, max_iterations: usize) -> &mut Self {
+    self.try_find_params_parallel(max_iterations).unwrap()
+  }
+
+  /// Candidates are checked in chunks but in the canonical order of
+  /// [`VfhmBuilder::try_find_params`], so both pick the same params for the same keys and search
+  /// seed.
+  pub fn try_find_params_parallel(
+    &mut self,
+    max_iterations: usize,
+  ) -> Result<&mut Self, VfhmError> {
+    use rayon::prelude::*;
+
+    if self.keys.is_empty() {
+      return Err(VfhmError::NoKeys);
+    }
+
+    // Only committed on success so a failed search leaves the params as they were.
+    let mut params = skip_undersized(self.keys.len(), self.params, self.search_seed)?;
+
+    let mut best = (usize::MAX, params);
+    let mut remaining = max_iterations;
+    let mut candidates = Vec::with_capacity(PARALLEL_CHUNK_SIZE.min(max_iterations));
+
+    while remaining > 0 {
+      candidates.clear();
+
+      let mut overflowed = false;
+
+      for _ in 0..PARALLEL_CHUNK_SIZE.min(remaining) {
+        candidates.push(params);
+
+        match params.next_candidate(self.search_seed) {
+          Some(next) => params = next,
+          None => {
+            overflowed = true;
+            break;
+          }
+        }
+      }
+
+      remaining -= candidates.len();
+
+      let keys = &self.keys;
+      let collisions: Vec<usize> = candidates
+        .par_iter()
+        .map(|params| collisions(keys, *params))
+        .collect();
+
+      if let Some(position) = collisions.iter().position(|collisions| *collisions == 0) {
+        self.params = candidates[position];
+
+        return Ok(self);
+      }
+
+      for (collisions, params) in collisions.into_iter().zip(&candidates) {
+        if collisions < best.0 {
+          best = (collisions, *params);
+        }
+      }
+
+      if overflowed {
+        return Err(VfhmError::MaskOverflow);
+      }
+    }
+
+    Err(search_exhausted(&self.keys, max_iterations, best))
+  }
+}
+
 impl<K, V> Default for VfhmBuilder<K, V> {
   fn default() -> Self {
     VfhmBuilder {
       keys: Vec::new(),
+      values: Vec::new(),
       params: VfhmParams(0, 1, 0, (usize::MAX, 0)),
-      _values: PhantomData::<V>,
+      search_seed: 0,
     }
   }
 }
+
+impl<K, V> Vfhm<K, V>
+where
+  K: VfhmKey,
+{
+  /// A key given more than once keeps its last value, like collecting into a `HashMap`.
+  pub fn try_from_iter<I>(iter: I, max_iterations: usize) -> Result<Self, VfhmError>
+  where
+    I: IntoIterator<Item = (K, V)>,
+  {
+    Ok(
+      VfhmBuilder::default()
+        .set_entries(dedup_entries(iter.into_iter().collect()))
+        .try_find_params(max_iterations)?
+        .build_populated(),
+    )
+  }
+
+  /// Inserts like [`Vfhm::try_insert`], but when the key collides with another key or falls outside
+  /// the bounds the params are searched again over every key and the table is rebuilt. Each rebuild
+  /// costs a full search over all keys, so this suits maps that grow rarely. When the search fails
+  /// the map keeps its entries and params, and the new entry is dropped.
+  pub fn insert_or_grow(&mut self, key: K, value: V) -> Result<Option<(K, V)>, VfhmError> {
+    let entry = match self.try_insert(key, value) {
+      Ok(output) => return Ok(output),
+      Err(entry) => entry,
+    };
+
+    let mut entries: Vec<_> = self.drain().collect();
+    entries.push(entry);
+
+    let mut builder = VfhmBuilder::default();
+    builder.set_entries(entries);
+
+    if let Err(error) = builder.try_find_params(DEFAULT_MAX_ITERATIONS) {
+      builder.keys.pop();
+      builder.values.pop();
+      self.extend(builder.keys.into_iter().zip(builder.values));
+
+      return Err(error);
+    }
+
+    *self = builder.build_populated();
+
+    Ok(None)
+  }
+
+  /// Searches params for the live keys with [`VfhmBuilder::try_find_params_min_size`] and moves
+  /// the entries into the tighter table, keeping the bounds. The map is left as is when the search
+  /// fails or doesn't find a smaller table.
+  pub fn rebuild_min(&mut self, max_iterations: usize) -> Result<(), VfhmError> {
+    let mut builder = VfhmBuilder::default();
+    builder
+      .set_entries(self.drain().collect())
+      .set_bounds(self.params.bounds());
+
+    let shrunk = builder
+      .try_find_params_min_size(max_iterations)
+      .map(|builder| builder.params.mask_size() < self.params.mask_size());
+
+    if let Ok(true) = shrunk {
+      *self = builder.build_populated();
+    } else {
+      self.extend(builder.keys.into_iter().zip(builder.values));
+    }
+
+    shrunk.map(drop)
+  }
+}
+
+/// Panics when no conflictless params are found for the keys, see [`Vfhm::try_from_iter`] for a
+/// fallible alternative.
+impl<K, V> FromIterator<(K, V)> for Vfhm<K, V>
+where
+  K: VfhmKey,
+{
+  fn from_iter<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+  {
+    Vfhm::try_from_iter(iter, DEFAULT_MAX_ITERATIONS).unwrap()
+  }
+}
+
+/// Runs the params search over the keys like [`FromIterator`] and panics when no conflictless
+/// params are found, use [`Vfhm::try_from_iter`] for a fallible alternative.
+///
+/// ```
+/// use vfhm::Vfhm;
+///
+/// let hashmap = Vfhm::from([("monday", 2), ("tuesday", 3)]);
+///
+/// assert_eq!(hashmap.get("monday"), Some(&2));
+/// assert_eq!(hashmap.get("tuesday"), Some(&3));
+///
+/// // The last value wins for a repeated key.
+/// let hashmap = Vfhm::from([("monday", 1), ("monday", 2)]);
+///
+/// assert_eq!(hashmap.len(), 1);
+/// assert_eq!(hashmap.get("monday"), Some(&2));
+/// ```
+impl<K, V, const N: usize> From<[(K, V); N]> for Vfhm<K, V>
+where
+  K: VfhmKey,
+{
+  fn from(entries: [(K, V); N]) -> Self {
+    Vfhm::from_iter(entries)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{dedup_entries, search_params, VfhmBuilder};
+  use crate::{Vfhm, VfhmError, VfhmParams};
+
+  #[test]
+  fn emit_static() {
+    let mut builder = VfhmBuilder::<_, ()>::default();
+    builder.set_keys(vec!["sunday", "monday", "tuesday"]);
+
+    let source = builder.find_params(1000).emit_static("Days");
+
+    assert!(syn::parse_file(&source).is_ok());
+    assert!(source.contains("pub struct Days;"));
+    assert!(source.contains("const BONDS: (usize, usize) = (6, 7);"));
+    assert!(source.contains("pub type DaysMap<K, V>"));
+  }
+
+  #[test]
+  fn find_params_min_size() {
+    let keys = vec![
+      "await",
+      "break",
+      "case",
+      "catch",
+      "class",
+      "const",
+      "continue",
+      "debugger",
+      "default",
+      "delete",
+      "do",
+      "else",
+      "enum",
+      "export",
+      "extends",
+      "false",
+      "finally",
+      "for",
+      "function",
+      "if",
+      "implements",
+      "import",
+      "in",
+      "instanceof",
+      "interface",
+      "let",
+      "new",
+      "null",
+      "package",
+      "private",
+      "protected",
+      "public",
+      "return",
+      "super",
+      "switch",
+      "static",
+      "this",
+      "throw",
+      "try",
+      "true",
+      "typeof",
+      "var",
+      "void",
+      "while",
+      "with",
+      "yield",
+    ];
+
+    let mut first_fit = VfhmBuilder::<_, ()>::default();
+    first_fit.set_keys(keys.clone()).find_params(1_000_000);
+
+    let mut min_size = VfhmBuilder::<_, usize>::default();
+    let mut hashmap = min_size
+      .set_keys(keys.clone())
+      .find_params_min_size(100_000)
+      .build();
+
+    for (index, key) in keys.iter().enumerate() {
+      hashmap.insert(key, index);
+    }
+
+    for (index, key) in keys.iter().enumerate() {
+      assert_eq!(hashmap.get(key), Some(&index));
+    }
+
+    // First fit settles on 256 slots, 128 are enough with more seeds.
+    assert!(min_size.into_params().mask_size() < first_fit.into_params().mask_size());
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn find_params_timed() {
+    use std::time::Duration;
+
+    let mut builder = VfhmBuilder::<_, ()>::default();
+    builder.set_keys(vec!["a\0", "b\0"]);
+
+    let params = builder.params;
+
+    // Every seed maps both keys to the same slot.
+    let result = builder
+      .find_params_timed(Duration::from_millis(5))
+      .map(|_| ());
+
+    assert!(matches!(result, Err(VfhmError::SearchExhausted { .. })));
+    assert_eq!(builder.params, params);
+
+    assert!(VfhmBuilder::<_, ()>::default()
+      .set_keys(vec!["sunday", "monday", "tuesday"])
+      .find_params_timed(Duration::from_secs(1))
+      .is_ok());
+  }
+
+  #[test]
+  fn rebuild_min() {
+    let keys = vec![
+      "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default",
+      "delete", "do", "else", "enum", "export", "extends", "false", "finally", "for",
+    ];
+
+    let mut hashmap: Vfhm<_, _> = keys.iter().copied().zip(0..).collect();
+    let mask_size = hashmap.params.mask_size();
+
+    for key in &keys[3..] {
+      hashmap.remove(key);
+    }
+
+    hashmap.rebuild_min(1_000_000).unwrap();
+
+    assert!(hashmap.params.mask_size() < mask_size);
+    assert_eq!(hashmap.len(), 3);
+    assert_eq!(hashmap.get("await"), Some(&0));
+    assert_eq!(hashmap.get("break"), Some(&1));
+    assert_eq!(hashmap.get("case"), Some(&2));
+    assert_eq!(hashmap.get("do"), None);
+  }
+
+  #[test]
+  fn rebuild_min_keeps_params() {
+    let keys = ["await", "break", "case"];
+
+    let mut hashmap = Vfhm::with_params(VfhmParams(0, 0xff, 0, (1, 20)));
+    hashmap.extend(keys.iter().copied().zip(0..));
+    hashmap.rebuild_min(1_000_000).unwrap();
+
+    assert!(hashmap.params.mask_size() < 256);
+    assert_eq!(hashmap.params.bounds(), (1, 20));
+
+    let keys = ["enum", "this", "void", "with", "null", "true", "break"];
+    let params = VfhmParams(13, 0b111 << 11, 11, (4, 5));
+
+    let mut fixed = Vfhm::with_params(params);
+    fixed.extend(keys.iter().copied().zip(0..));
+
+    // Enough for the first-fit search to land on 16 slots, but not to find these 8.
+    fixed.rebuild_min(530).unwrap();
+
+    assert_eq!(fixed.params, params);
+    assert_eq!(fixed.len(), keys.len());
+    assert_eq!(fixed.get("break"), Some(&6));
+  }
+
+  proptest::proptest! {
+    #[test]
+    fn separates_keys(
+      keys in proptest::collection::hash_set("[a-z]{1,8}", 1..16),
+      probes in proptest::collection::vec("[a-z]{1,8}", 0..16),
+    ) {
+      let keys: Vec<String> = keys.into_iter().collect();
+
+      let mut builder = VfhmBuilder::default();
+      builder.set_entries(keys.iter().cloned().zip(0..).collect());
+
+      proptest::prop_assume!(builder.try_find_params(100_000).is_ok());
+
+      let hashmap = builder.build_populated();
+
+      for (index, key) in keys.iter().enumerate() {
+        proptest::prop_assert_eq!(hashmap.get(key.as_str()), Some(&index));
+      }
+
+      for probe in probes.iter().filter(|probe| !keys.contains(probe)) {
+        proptest::prop_assert_eq!(hashmap.get(probe.as_str()), None);
+      }
+    }
+  }
+
+  #[test]
+  fn insert_or_grow() {
+    let days = [
+      "sunday",
+      "monday",
+      "tuesday",
+      "wednesday",
+      "thursday",
+      "firday",
+      "saturday",
+    ];
+
+    let mut hashmap = Vfhm::with_params(VfhmParams(0, 0, 0, (6, 6)));
+
+    for (value, day) in (1..).zip(days) {
+      assert_eq!(hashmap.insert_or_grow(day, value), Ok(None));
+    }
+
+    assert!(hashmap.params.mask_size() > 1);
+    assert_eq!(hashmap.len(), days.len());
+
+    for (value, day) in (1..).zip(days) {
+      assert_eq!(hashmap.get(day), Some(&value));
+    }
+
+    assert_eq!(hashmap.insert_or_grow("monday", 8), Ok(Some(("monday", 2))));
+  }
+
+  #[test]
+  fn insert_or_grow_failure() {
+    let mut hashmap = Vfhm::with_params(VfhmParams(0, 1, 0, (2, 2)));
+    hashmap.insert("a\0", 1);
+
+    let params = hashmap.params;
+
+    // Every seed maps both keys to the same slot.
+    assert!(hashmap.insert_or_grow("b\0", 2).is_err());
+    assert_eq!(hashmap.params, params);
+    assert_eq!(hashmap.len(), 1);
+    assert_eq!(hashmap.get("a\0"), Some(&1));
+  }
+
+  #[test]
+  fn reproducible_search() {
+    let keys = vec![
+      "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default",
+    ];
+
+    let params = |search_seed| {
+      let mut builder = VfhmBuilder::<_, ()>::default();
+      builder
+        .set_keys(keys.clone())
+        .search_seed(search_seed)
+        .find_params(100_000);
+
+      builder.into_params()
+    };
+
+    assert_eq!(params(0), params(0));
+    assert_eq!(params(1_000), params(1_000));
+    assert!(params(1_000).seed() >= 1_000);
+  }
+
+  #[test]
+  fn with_capacity() {
+    let keys = vec!["sunday", "monday", "tuesday"];
+
+    let mut builder = VfhmBuilder::<_, ()>::with_capacity(100);
+    builder.set_keys(keys.clone()).find_params(1000);
+
+    assert!(builder.params.mask_size() >= 200);
+    assert!(keys
+      .iter()
+      .all(|key| builder.build().params.bound_check(key)));
+  }
+
+  #[test]
+  fn mask_overflow() {
+    // Every seed maps both keys to the same slot, start from the last candidate at the widest mask
+    // instead of walking every seed up to it.
+    let last = VfhmParams::MAX_MASK_SIZE - 1;
+    let builder = || {
+      let mut builder = VfhmBuilder::<_, ()>::default();
+      builder.set_keys(vec!["a\0", "b\0"]);
+      builder.params = VfhmParams(last, last, 0, builder.params.bounds());
+      builder
+    };
+
+    assert_eq!(
+      builder().try_find_params(usize::MAX).map(|_| ()),
+      Err(VfhmError::MaskOverflow)
+    );
+    assert_eq!(
+      builder().try_find_params_min_size(usize::MAX).map(|_| ()),
+      Err(VfhmError::MaskOverflow)
+    );
+  }
+
+  #[test]
+  fn more_keys_than_the_sqrt_rule_covers() {
+    // Hashes to `first - len * seed`, which is unique per key once the seed passes 253.
+    let keys: Vec<Vec<u8>> = (2..=255)
+      .flat_map(|first| (1..=20).map(move |len| [vec![first], vec![1; len - 1]].concat()))
+      .collect();
+
+    let hashmap = VfhmBuilder::default()
+      .set_entries(keys.iter().map(Vec::as_slice).zip(0..).collect())
+      .find_params(1000)
+      .build_populated();
+
+    assert!(keys.len() > 4096);
+    assert_eq!(hashmap.len(), keys.len());
+
+    for (value, key) in keys.iter().enumerate() {
+      assert_eq!(hashmap.get(key.as_slice()), Some(&value));
+    }
+  }
+
+  #[test]
+  fn find_params_or_overflow() {
+    let mut builder = VfhmBuilder::default();
+    builder.set_entries(vec![
+      ("do", 1),
+      ("a\0", 2),
+      ("if", 3),
+      ("b\0", 4),
+      ("for", 5),
+    ]);
+
+    // "a\0" and "b\0" share a slot under every params.
+    let (params, overflow) = builder.find_params_or_overflow(10_000);
+
+    assert_eq!(overflow, vec!["b\0"]);
+    assert!(builder
+      .collision_report(params)
+      .iter()
+      .all(|(_, keys)| keys.len() == 1));
+
+    let hashmap = builder.build_populated();
+
+    assert_eq!(hashmap.len(), 4);
+    assert_eq!(hashmap.get("do"), Some(&1));
+    assert_eq!(hashmap.get("a\0"), Some(&2));
+    assert_eq!(hashmap.get("if"), Some(&3));
+    assert_eq!(hashmap.get("for"), Some(&5));
+    assert_eq!(hashmap.get("b\0"), None);
+
+    let (_, overflow) = VfhmBuilder::<_, ()>::default()
+      .set_keys(vec!["sunday", "monday", "tuesday"])
+      .find_params_or_overflow(1000);
+
+    assert!(overflow.is_empty());
+  }
+
+  #[test]
+  fn set_keys_from_iterator() {
+    let source = "sunday\n# holidays\nmonday\n\ntuesday\n";
+
+    let mut builder = VfhmBuilder::default();
+    builder
+      .set_keys(
+        source
+          .lines()
+          .filter(|line| !line.is_empty() && !line.starts_with('#')),
+      )
+      .find_params(1000);
+
+    let mut hashmap = builder.build();
+    hashmap.extend([("sunday", 1), ("monday", 2), ("tuesday", 3)]);
+
+    assert_eq!(builder.into_params().bounds(), (6, 7));
+    assert_eq!(hashmap.get("tuesday"), Some(&3));
+    assert_eq!(hashmap.len(), 3);
+  }
+
+  #[test]
+  fn search_params_directly() {
+    let days = [
+      "sunday",
+      "monday",
+      "tuesday",
+      "wednesday",
+      "thursday",
+      "friday",
+      "saturday",
+    ];
+
+    let params = search_params(&days, 100_000).unwrap();
+
+    assert_eq!(params.bounds(), (6, 9));
+    assert_eq!(
+      params,
+      VfhmBuilder::<_, ()>::default()
+        .set_keys(days)
+        .find_params(100_000)
+        .build()
+        .params
+    );
+
+    let mut hashmap = Vfhm::with_params(params);
+    hashmap.extend(days.into_iter().zip(1..));
+
+    assert_eq!(hashmap.len(), 7);
+    assert_eq!(hashmap.get("thursday"), Some(&5));
+
+    assert_eq!(search_params::<&str>(&[], 1000), Err(VfhmError::NoKeys));
+  }
+
+  #[test]
+  fn duplicate_keys() {
+    let hashmap = Vfhm::try_from_iter([("a", 1), ("b", 2), ("a", 3)], 1000).unwrap();
+
+    assert_eq!(hashmap.len(), 2);
+    assert_eq!(hashmap.get("a"), Some(&3));
+    assert_eq!(hashmap.get("b"), Some(&2));
+
+    let hashmap: Vfhm<_, _> = [("sunday", 1), ("sunday", 2)].into_iter().collect();
+
+    assert_eq!(hashmap.len(), 1);
+    assert_eq!(hashmap.get("sunday"), Some(&2));
+
+    // Distinct keys sharing an unmasked hash stay apart.
+    assert_eq!(
+      dedup_entries(vec![
+        ("a\0", 1),
+        ("b\0", 2),
+        ("a\0", 3),
+        ("b\0", 4),
+        ("c", 5)
+      ]),
+      vec![("a\0", 3), ("b\0", 4), ("c", 5)]
+    );
+  }
+
+  #[test]
+  fn set_bounds() {
+    let mut hashmap = VfhmBuilder::default()
+      .set_keys(vec!["sunday", "monday", "tuesday"])
+      .set_bounds((6, 12))
+      .find_params(1000)
+      .build();
+
+    hashmap.insert("wednesday", 4);
+
+    assert_eq!(hashmap.get("wednesday"), Some(&4));
+  }
+
+  #[test]
+  fn set_bounds_keeps_keys() {
+    let hashmap = VfhmBuilder::default()
+      .set_entries(vec![("sunday", 0), ("wednesday", 3)])
+      .set_bounds((6, 6))
+      .find_params(1000)
+      .build_populated();
+
+    assert_eq!(hashmap.params.bounds(), (6, 9));
+    assert_eq!(hashmap.get("wednesday"), Some(&3));
+  }
+
+  #[cfg(feature = "rayon")]
+  #[test]
+  fn find_params_parallel() {
+    let keys = vec![
+      "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default",
+      "delete", "do", "else", "enum", "export", "extends", "false", "finally", "for",
+    ];
+
+    let mut sequential = VfhmBuilder::<_, usize>::default();
+    sequential.set_keys(keys.clone()).find_params(100_000);
+
+    let mut parallel = VfhmBuilder::<_, usize>::default();
+    let mut hashmap = parallel
+      .set_keys(keys.clone())
+      .find_params_parallel(100_000)
+      .build();
+
+    for (index, key) in keys.iter().enumerate() {
+      hashmap.insert(key, index);
+    }
+
+    for (index, key) in keys.iter().enumerate() {
+      assert_eq!(hashmap.get(key), Some(&index));
+    }
+
+    assert_eq!(sequential.into_params(), parallel.into_params());
+  }
+
+  #[cfg(feature = "rayon")]
+  #[test]
+  fn find_params_parallel_failure() {
+    let mut builder = VfhmBuilder::<_, ()>::default();
+    builder.set_keys(vec!["a\0", "b\0"]);
+
+    let params = builder.params;
+
+    // Every seed maps both keys to the same slot.
+    let result = builder.try_find_params_parallel(1000).map(|_| ());
+
+    assert!(matches!(result, Err(VfhmError::SearchExhausted { .. })));
+    assert_eq!(builder.params, params);
+  }
+}
@@ -0,0 +1,103 @@
+use core::mem;
+
+pub enum Entry<'a, K, V> {
+  Occupied(OccupiedEntry<'a, K, V>),
+  Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+  pub fn key(&self) -> &K {
+    match self {
+      Entry::Occupied(entry) => entry.key(),
+      Entry::Vacant(entry) => entry.key(),
+    }
+  }
+
+  pub fn or_insert(self, default: V) -> &'a mut V {
+    self.or_insert_with(|| default)
+  }
+
+  pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+  where
+    F: FnOnce() -> V,
+  {
+    match self {
+      Entry::Occupied(entry) => entry.into_mut(),
+      Entry::Vacant(entry) => entry.insert(default()),
+    }
+  }
+
+  pub fn and_modify<F>(mut self, f: F) -> Self
+  where
+    F: FnOnce(&mut V),
+  {
+    if let Entry::Occupied(entry) = &mut self {
+      f(entry.get_mut());
+    }
+
+    self
+  }
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+  V: Default,
+{
+  pub fn or_default(self) -> &'a mut V {
+    self.or_insert_with(V::default)
+  }
+}
+
+pub struct OccupiedEntry<'a, K, V> {
+  pub(crate) entry: &'a mut (K, V),
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+  pub fn key(&self) -> &K {
+    &self.entry.0
+  }
+
+  pub fn get(&self) -> &V {
+    &self.entry.1
+  }
+
+  pub fn get_mut(&mut self) -> &mut V {
+    &mut self.entry.1
+  }
+
+  pub fn into_mut(self) -> &'a mut V {
+    &mut self.entry.1
+  }
+
+  pub fn insert(&mut self, value: V) -> V {
+    mem::replace(&mut self.entry.1, value)
+  }
+}
+
+/// The slot of a vacant entry may still hold a different key that shares its index, inserting
+/// overwrites it the same way [`Vfhm::insert`](crate::Vfhm::insert) does.
+pub struct VacantEntry<'a, K, V> {
+  pub(crate) key: K,
+  pub(crate) slot: &'a mut Option<(K, V)>,
+  pub(crate) length: &'a mut usize,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+  pub fn key(&self) -> &K {
+    &self.key
+  }
+
+  pub fn into_key(self) -> K {
+    self.key
+  }
+
+  pub fn insert(self, value: V) -> &'a mut V {
+    if self.slot.is_none() {
+      *self.length += 1;
+    }
+
+    let (_, value) = self.slot.insert((self.key, value));
+
+    value
+  }
+}
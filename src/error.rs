@@ -0,0 +1,48 @@
+use alloc::vec::Vec;
+use core::{error::Error, fmt};
+
+use crate::VfhmParams;
+
+pub type CollisionReport = Vec<(usize, Vec<usize>)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VfhmError {
+  NoKeys,
+  SearchExhausted {
+    iterations: usize,
+    best_collisions: usize,
+    best_report: CollisionReport,
+  },
+  InvalidBounds {
+    lower: usize,
+    upper: usize,
+  },
+  MaskOverflow,
+}
+
+impl fmt::Display for VfhmError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      VfhmError::NoKeys => write!(f, "no keys were provided to the builder"),
+      VfhmError::SearchExhausted {
+        iterations,
+        best_collisions,
+        ..
+      } => write!(
+        f,
+        "no conflictless params found in {iterations} iterations (best had {best_collisions} \
+         collisions)"
+      ),
+      VfhmError::InvalidBounds { lower, upper } => {
+        write!(f, "lower bound {lower} is above upper bound {upper}")
+      }
+      VfhmError::MaskOverflow => write!(
+        f,
+        "the mask can't grow past {} slots",
+        VfhmParams::MAX_MASK_SIZE
+      ),
+    }
+  }
+}
+
+impl Error for VfhmError {}
@@ -0,0 +1,124 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::borrow::Borrow;
+
+use crate::{Vfhm, VfhmKey, VfhmParams};
+
+/// Lookup-only view of a [`Vfhm`], `Send + Sync` whenever `K` and `V` are.
+#[derive(Debug, Clone)]
+pub struct FrozenVfhm<K, V> {
+  table: Box<[Option<(K, V)>]>,
+  params: VfhmParams,
+  length: usize,
+}
+
+/// Cheap to clone and read from any number of threads without locking.
+pub type SharedVfhm<K, V> = Arc<FrozenVfhm<K, V>>;
+
+impl<K, V> Vfhm<K, V> {
+  pub fn freeze(self) -> FrozenVfhm<K, V> {
+    FrozenVfhm {
+      table: self.table.into_boxed_slice(),
+      params: self.params,
+      length: self.length,
+    }
+  }
+
+  pub fn share(self) -> SharedVfhm<K, V> {
+    Arc::new(self.freeze())
+  }
+}
+
+impl<K, V> FrozenVfhm<K, V>
+where
+  K: VfhmKey,
+{
+  pub fn len(&self) -> usize {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    match &self.table[key.table_key(self.params)] {
+      Some((k, value)) if key.table_key_compare(k.borrow()) => Some(value),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{sync::Arc, thread};
+
+  use crate::builder::VfhmBuilder;
+
+  #[test]
+  fn shared_across_threads() {
+    let keys = vec!["sunday", "monday", "tuesday", "wednesday"];
+
+    let mut builder = VfhmBuilder::default();
+    builder
+      .set_entries(keys.iter().copied().zip(1..).collect())
+      .find_params(1000);
+
+    let frozen = Arc::new(builder.build_populated().freeze());
+
+    let handles: Vec<_> = (0..4)
+      .map(|_| {
+        let frozen = Arc::clone(&frozen);
+        let keys = keys.clone();
+
+        thread::spawn(move || {
+          for (value, key) in (1..).zip(keys) {
+            assert_eq!(frozen.get(key), Some(&value));
+          }
+
+          assert!(!frozen.contains_key("friday"));
+          assert_eq!(frozen.len(), 4);
+        })
+      })
+      .collect();
+
+    for handle in handles {
+      handle.join().unwrap();
+    }
+  }
+
+  #[test]
+  fn share() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<crate::Vfhm<&str, i32>>();
+    assert_send_sync::<super::SharedVfhm<&str, i32>>();
+
+    let shared = crate::Vfhm::from([("sunday", 1), ("monday", 2), ("tuesday", 3)]).share();
+
+    thread::scope(|scope| {
+      for _ in 0..4 {
+        scope.spawn(|| {
+          assert_eq!(shared.get("sunday"), Some(&1));
+          assert_eq!(shared.get("tuesday"), Some(&3));
+          assert_eq!(shared.get("friday"), None);
+        });
+      }
+    });
+  }
+}
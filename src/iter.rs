@@ -0,0 +1,108 @@
+use alloc::vec;
+use core::slice;
+
+#[derive(Debug, Clone)]
+pub struct Iter<'a, K, V> {
+  pub(crate) inner: slice::Iter<'a, Option<(K, V)>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .inner
+      .find_map(|slot| slot.as_ref().map(|(key, value)| (key, value)))
+  }
+}
+
+#[derive(Debug)]
+pub struct IterMut<'a, K, V> {
+  pub(crate) inner: slice::IterMut<'a, Option<(K, V)>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+  type Item = (&'a K, &'a mut V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .inner
+      .find_map(|slot| slot.as_mut().map(|(key, value)| (&*key, value)))
+  }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<K, V> {
+  pub(crate) inner: vec::IntoIter<Option<(K, V)>>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+  type Item = (K, V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.find_map(|slot| slot)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keys<'a, K, V> {
+  pub(crate) inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+  type Item = &'a K;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.next().map(|(key, _)| key)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Values<'a, K, V> {
+  pub(crate) inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+  type Item = &'a V;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.next().map(|(_, value)| value)
+  }
+}
+
+#[derive(Debug)]
+pub struct ValuesMut<'a, K, V> {
+  pub(crate) inner: IterMut<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+  type Item = &'a mut V;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.next().map(|(_, value)| value)
+  }
+}
+
+/// Remaining entries are dropped along with the iterator, so the map is always left empty.
+#[derive(Debug)]
+pub struct Drain<'a, K, V> {
+  pub(crate) inner: slice::IterMut<'a, Option<(K, V)>>,
+  pub(crate) length: &'a mut usize,
+}
+
+impl<K, V> Iterator for Drain<'_, K, V> {
+  type Item = (K, V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let entry = self.inner.find_map(Option::take)?;
+    *self.length -= 1;
+
+    Some(entry)
+  }
+}
+
+impl<K, V> Drop for Drain<'_, K, V> {
+  fn drop(&mut self) {
+    self.for_each(drop);
+  }
+}
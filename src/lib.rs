@@ -1,10 +1,53 @@
-use std::borrow::Borrow;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+#[cfg(any(test, feature = "derive"))]
+extern crate self as vfhm;
+
+use alloc::vec::Vec;
+use core::{
+  borrow::Borrow,
+  fmt, mem,
+  ops::{AddAssign, Index, IndexMut},
+};
+
+#[cfg(feature = "derive")]
+pub use vfhm_derive::{perfect_map, VfhmStaticMap};
+
+use crate::scheme::{hash_bytes, Multiplicative};
+pub use crate::{
+  entry::{Entry, OccupiedEntry, VacantEntry},
+  error::VfhmError,
+  iter::{Drain, IntoIter, Iter, IterMut, Keys, Values, ValuesMut},
+};
+
+#[cfg(feature = "rkyv")]
+mod archive;
+pub mod array;
+#[cfg(feature = "smallvec")]
+pub mod bucketed;
 #[cfg(feature = "builder")]
 pub mod builder;
+mod entry;
+mod error;
+pub mod frozen;
+mod iter;
+#[cfg(feature = "phf-compat")]
+mod phf_compat;
+pub mod probing;
+pub mod routed;
+pub mod scheme;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod set;
+pub mod soa;
 pub mod r#static;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
+#[cfg_attr(
+  feature = "rkyv",
+  derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct Vfhm<K, V> {
   table: Vec<Option<(K, V)>>,
   params: VfhmParams,
@@ -24,6 +67,177 @@ impl<K, V> Vfhm<K, V> {
       length: 0,
     }
   }
+
+  /// See [`VfhmParams::with_capacity`] for how the table is sized.
+  pub fn with_capacity(capacity: usize) -> Self {
+    Vfhm::with_params(VfhmParams::with_capacity(capacity))
+  }
+
+  pub fn iter(&self) -> Iter<'_, K, V> {
+    Iter {
+      inner: self.table.iter(),
+    }
+  }
+
+  pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+    IterMut {
+      inner: self.table.iter_mut(),
+    }
+  }
+
+  pub fn clear(&mut self) {
+    self.table.iter_mut().for_each(|slot| *slot = None);
+    self.length = 0;
+  }
+
+  /// Entries are yielded in table-index order, keeping the allocation for reuse.
+  pub fn drain(&mut self) -> Drain<'_, K, V> {
+    Drain {
+      inner: self.table.iter_mut(),
+      length: &mut self.length,
+    }
+  }
+
+  pub fn retain<F>(&mut self, mut f: F)
+  where
+    F: FnMut(&K, &mut V) -> bool,
+  {
+    for slot in &mut self.table {
+      if let Some((key, value)) = slot {
+        if !f(key, value) {
+          *slot = None;
+          self.length -= 1;
+        }
+      }
+    }
+  }
+
+  pub fn map_values<W, F>(self, mut f: F) -> Vfhm<K, W>
+  where
+    F: FnMut(V) -> W,
+  {
+    Vfhm {
+      table: self
+        .table
+        .into_iter()
+        .map(|slot| slot.map(|(key, value)| (key, f(value))))
+        .collect(),
+      params: self.params,
+      length: self.length,
+    }
+  }
+
+  pub fn keys(&self) -> Keys<'_, K, V> {
+    Keys { inner: self.iter() }
+  }
+
+  pub fn values(&self) -> Values<'_, K, V> {
+    Values { inner: self.iter() }
+  }
+
+  pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+    ValuesMut {
+      inner: self.iter_mut(),
+    }
+  }
+
+  /// Number of addressable slots, which is [`VfhmParams::mask_size`] and usually more than the
+  /// number of entries.
+  pub fn capacity(&self) -> usize {
+    self.params.mask_size()
+  }
+
+  /// Heap size of the table plus the map itself, grows with [`VfhmParams::mask_size`] rather than
+  /// with the number of entries.
+  pub fn memory_usage(&self) -> usize {
+    self.table.capacity() * mem::size_of::<Option<(K, V)>>() + mem::size_of::<Self>()
+  }
+
+  /// Scans every slot, so this is O(mask size) rather than a hashed lookup.
+  pub fn find_key<F>(&self, pred: F) -> Option<&K>
+  where
+    F: Fn(&V) -> bool,
+  {
+    self
+      .iter()
+      .find_map(|(key, value)| pred(value).then_some(key))
+  }
+
+  /// Entries ordered by key, independent of the params and table layout.
+  pub fn to_sorted_vec(&self) -> Vec<(&K, &V)>
+  where
+    K: Ord,
+  {
+    let mut entries: Vec<_> = self.iter().collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+
+    entries
+  }
+}
+
+/// Only the occupied slots are printed, large masks are mostly empty.
+impl<K, V> fmt::Debug for Vfhm<K, V>
+where
+  K: fmt::Debug,
+  V: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    struct Entries<'a, K, V>(&'a Vfhm<K, V>);
+
+    impl<K, V> fmt::Debug for Entries<'_, K, V>
+    where
+      K: fmt::Debug,
+      V: fmt::Debug,
+    {
+      fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.iter()).finish()
+      }
+    }
+
+    f.debug_struct("Vfhm")
+      .field("len", &self.length)
+      .field("params", &self.params)
+      .field("mask_size", &self.params.mask_size())
+      .field("entries", &Entries(self))
+      .finish()
+  }
+}
+
+/// Uses the mask the builder starts its search from, bounds reject every key so the map isn't
+/// useful until [`Vfhm::with_params`] or the builder provides real params.
+impl<K, V> Default for Vfhm<K, V> {
+  fn default() -> Self {
+    Vfhm::with_params(VfhmParams(0, 1, 0, (usize::MAX, usize::MAX)))
+  }
+}
+
+impl<K, V> IntoIterator for Vfhm<K, V> {
+  type Item = (K, V);
+  type IntoIter = IntoIter<K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter {
+      inner: self.table.into_iter(),
+    }
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a Vfhm<K, V> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = Iter<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut Vfhm<K, V> {
+  type Item = (&'a K, &'a mut V);
+  type IntoIter = IterMut<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
 }
 
 impl<K, V> Vfhm<K, V>
@@ -38,21 +252,55 @@ where
     self.length == 0
   }
 
-  pub fn contains_key<Q>(&self, key: Q) -> bool
+  pub fn load_factor(&self) -> f64 {
+    self.length as f64 / self.params.mask_size() as f64
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  /// Scans every slot, O(mask size) like [`Vfhm::find_key`].
+  pub fn contains_value(&self, value: &V) -> bool
+  where
+    V: PartialEq,
+  {
+    self.values().any(|v| v == value)
+  }
+
+  #[inline(always)]
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.get_key_value(key).map(|(_, value)| value)
+  }
+
+  /// [`Vfhm::get`] for small `Copy` values like integers or `&'static str`, returned by value.
+  #[inline(always)]
+  pub fn get_copied<Q>(&self, key: &Q) -> Option<V>
   where
-    Q: Borrow<K>,
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+    V: Copy,
   {
-    self.params.bound_check(key.borrow()) && self.get(key).is_some()
+    self.get(key).copied()
   }
 
-  pub fn get<Q>(&self, key: Q) -> Option<&V>
+  #[inline(always)]
+  pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
   where
-    Q: Borrow<K>,
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
   {
     let Vfhm {
       ref table, params, ..
     } = *self;
-    let key = key.borrow();
 
     if !self.params.bound_check(key) {
       return None;
@@ -62,10 +310,110 @@ where
 
     table[index]
       .iter()
-      .find(|(k, _)| key.table_key_compare(k))
+      .find(|(k, _)| key.table_key_compare(k.borrow()))
+      .map(|(key, value)| (key, value))
+  }
+  /// Skips the [`VfhmParams::bound_check`] length gate for callers that already filtered key
+  /// lengths. Still safe, the slot index is always bounded by the mask.
+  #[inline(always)]
+  pub fn get_unchecked<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.table[key.table_key(self.params)]
+      .as_ref()
+      .filter(|(k, _)| key.table_key_compare(k.borrow()))
+      .map(|(_, value)| value)
+  }
+
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    let Vfhm {
+      ref mut table,
+      params,
+      ..
+    } = *self;
+
+    if !params.bound_check(key) {
+      return None;
+    }
+
+    let index = key.table_key(params);
+
+    table[index]
+      .iter_mut()
+      .find(|(k, _)| key.table_key_compare(k.borrow()))
       .map(|(_, value)| value)
   }
 
+  /// Returns `None` when any key is missing or when two keys share a table slot.
+  pub fn get_disjoint_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    let mut indices = [0; N];
+
+    for (index, key) in indices.iter_mut().zip(keys) {
+      if !self.contains_key(key) {
+        return None;
+      }
+
+      *index = key.table_key(self.params);
+    }
+
+    let slots = self.table.get_disjoint_mut(indices).ok()?;
+
+    Some(slots.map(|slot| &mut slot.as_mut().expect("slot was checked").1))
+  }
+
+  /// Hashes every key before comparing any of them, same results as calling [`Vfhm::get`] for
+  /// each key.
+  pub fn get_batch<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    let indices = keys.map(|key| {
+      self
+        .params
+        .bound_check(key)
+        .then(|| key.table_key(self.params))
+    });
+
+    core::array::from_fn(|position| match &self.table[indices[position]?] {
+      Some((k, value)) if keys[position].table_key_compare(k.borrow()) => Some(value),
+      _ => None,
+    })
+  }
+
+  /// Splits `text` on non-alphabetic characters and looks up every token, tokens with a length
+  /// outside the bounds are rejected before hashing.
+  pub fn scan<'a, 'b>(
+    &'a self,
+    text: &'b str,
+  ) -> impl Iterator<Item = (&'b str, Option<&'a V>)> + use<'a, 'b, K, V>
+  where
+    K: Borrow<str>,
+  {
+    text
+      .split(|c: char| !c.is_alphabetic())
+      .filter(|token| !token.is_empty())
+      .map(move |token| {
+        let value = self
+          .params
+          .len_in_bounds(token.len())
+          .then(|| self.get_unchecked(token))
+          .flatten();
+
+        (token, value)
+      })
+  }
+
   pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
     let Vfhm {
       ref mut table,
@@ -77,7 +425,7 @@ where
 
     let mut output = Some((key, value));
 
-    std::mem::swap(&mut output, &mut table[index]);
+    mem::swap(&mut output, &mut table[index]);
 
     if output.is_none() {
       self.length += 1;
@@ -86,9 +434,50 @@ where
     output
   }
 
-  pub fn remove<Q>(&mut self, key: K) -> Option<(K, V)>
+  pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+    let Vfhm {
+      ref mut table,
+      params,
+      ref mut length,
+    } = *self;
+
+    let slot = &mut table[key.table_key(params)];
+    let occupied = slot.as_ref().is_some_and(|(k, _)| key.table_key_compare(k));
+
+    match (occupied, slot) {
+      (true, Some(entry)) => Entry::Occupied(OccupiedEntry { entry }),
+      (_, slot) => Entry::Vacant(VacantEntry { key, slot, length }),
+    }
+  }
+
+  /// Hands the entry back when it can't be stored retrievably: the key is outside the bounds or
+  /// shares its slot with a different key.
+  pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<(K, V)>, (K, V)> {
+    if !self.params.bound_check(&key) {
+      return Err((key, value));
+    }
+
+    let index = key.table_key(self.params);
+
+    match &self.table[index] {
+      Some((k, _)) if !key.table_key_compare(k) => Err((key, value)),
+      _ => Ok(self.insert(key, value)),
+    }
+  }
+
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.remove_entry(key).map(|(_, value)| value)
+  }
+
+  /// Like [`Vfhm::remove`] but also hands back the stored key.
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
   where
-    Q: Borrow<K>,
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
   {
     let Vfhm {
       ref mut table,
@@ -96,11 +485,22 @@ where
       ..
     } = *self;
 
+    if !params.bound_check(key) {
+      return None;
+    }
+
     let index = key.table_key(params);
 
-    let mut output = None;
+    let slot = &mut table[index];
 
-    std::mem::swap(&mut output, &mut table[index]);
+    if !slot
+      .as_ref()
+      .is_some_and(|(k, _)| key.table_key_compare(k.borrow()))
+    {
+      return None;
+    }
+
+    let output = mem::take(slot);
 
     if output.is_some() {
       self.length -= 1;
@@ -108,32 +508,296 @@ where
 
     output
   }
+
+  /// Tightens the length bounds to the keys still in the map so misses on lengths no key uses
+  /// are rejected before hashing. Keys inserted afterwards must fall within the new bounds, an
+  /// empty map rejects every key like [`Vfhm::default`].
+  pub fn recompute_bounds(&mut self) {
+    let bounds = self
+      .keys()
+      .map(VfhmKey::key_len)
+      .fold(None, |bounds, len| match bounds {
+        Some((lower, upper)) => Some((len.min(lower), len.max(upper))),
+        None => Some((len, len)),
+      });
+
+    self
+      .params
+      .set_bounds(bounds.unwrap_or((usize::MAX, usize::MAX)));
+  }
+}
+
+impl<K, V> Vfhm<K, V>
+where
+  K: VfhmKey,
+  V: AddAssign + From<u8> + Default,
+{
+  /// Counts an occurrence of `key`, same slot rules as [`Vfhm::entry`].
+  pub fn increment(&mut self, key: K) -> &mut V {
+    let count = self.entry(key).or_default();
+    *count += V::from(1);
+
+    count
+  }
+}
+
+impl<K, V> PartialEq for Vfhm<K, V>
+where
+  K: VfhmKey,
+  V: PartialEq,
+{
+  // Skips the bound check, keys inserted outside the bounds are still in their slots.
+  fn eq(&self, other: &Self) -> bool {
+    self.len() == other.len()
+      && self
+        .iter()
+        .all(|(key, value)| other.get_unchecked(key) == Some(value))
+  }
+}
+
+impl<K, V> Eq for Vfhm<K, V>
+where
+  K: VfhmKey,
+  V: Eq,
+{
+}
+
+/// Pairs are inserted with [`Vfhm::insert`], so a key sharing a slot with an existing key
+/// overwrites it.
+impl<K, V> Extend<(K, V)> for Vfhm<K, V>
+where
+  K: VfhmKey,
+{
+  fn extend<I>(&mut self, iter: I)
+  where
+    I: IntoIterator<Item = (K, V)>,
+  {
+    for (key, value) in iter {
+      self.insert(key, value);
+    }
+  }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V> Vfhm<K, V>
+where
+  K: VfhmKey + Send,
+  V: Send,
+{
+  /// [`Extend::extend`] with the keys hashed in parallel. Hashing only reads the params, then the
+  /// slots are written serially in iteration order, so colliding keys overwrite each other exactly
+  /// like [`Vfhm::insert`] and nothing writes the table concurrently.
+  pub fn par_extend<I>(&mut self, iter: I)
+  where
+    I: rayon::iter::IntoParallelIterator<Item = (K, V)>,
+  {
+    use rayon::prelude::*;
+
+    let params = self.params;
+    let entries: Vec<(usize, (K, V))> = iter
+      .into_par_iter()
+      .map(|(key, value)| (key.table_key(params), (key, value)))
+      .collect();
+
+    for (index, entry) in entries {
+      if self.table[index].replace(entry).is_none() {
+        self.length += 1;
+      }
+    }
+  }
+}
+
+impl<K, V, Q> Index<&Q> for Vfhm<K, V>
+where
+  K: VfhmKey + Borrow<Q>,
+  Q: VfhmKey + ?Sized,
+{
+  type Output = V;
+
+  fn index(&self, key: &Q) -> &V {
+    self.get(key).expect("key is not present in Vfhm")
+  }
+}
+
+impl<K, V, Q> IndexMut<&Q> for Vfhm<K, V>
+where
+  K: VfhmKey + Borrow<Q>,
+  Q: VfhmKey + ?Sized,
+{
+  fn index_mut(&mut self, key: &Q) -> &mut V {
+    self.get_mut(key).expect("key is not present in Vfhm")
+  }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+  feature = "rkyv",
+  derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct VfhmParams(pub usize, pub usize, pub usize, pub (usize, usize));
 
 impl VfhmParams {
-  pub fn mask_size(&self) -> usize {
+  pub const fn new(seed: usize, mask: usize, mask_offset: usize, bounds: (usize, usize)) -> Self {
+    VfhmParams(seed, mask, mask_offset, bounds)
+  }
+
+  /// Picks an unshifted mask covering the low `log2(2 * capacity)` bits (rounded up), so the
+  /// table has at least twice as many slots as `capacity` and bounds that accept any key length.
+  /// The size saturates at [`VfhmParams::MAX_MASK_SIZE`].
+  pub const fn with_capacity(capacity: usize) -> Self {
+    let capacity = if capacity == 0 { 1 } else { capacity };
+    let mask_size = match capacity.checked_mul(2) {
+      Some(wanted) if wanted < Self::MAX_MASK_SIZE => wanted.next_power_of_two(),
+      _ => Self::MAX_MASK_SIZE,
+    };
+
+    VfhmParams(0, mask_size - 1, 0, (0, usize::MAX))
+  }
+
+  pub const fn seed(&self) -> usize {
+    self.0
+  }
+
+  pub const fn mask(&self) -> usize {
+    self.1
+  }
+
+  pub const fn mask_offset(&self) -> usize {
+    self.2
+  }
+
+  pub const fn bounds(&self) -> (usize, usize) {
+    self.3
+  }
+
+  pub const fn set_seed(&mut self, seed: usize) {
+    self.0 = seed;
+  }
+
+  pub const fn set_mask(&mut self, mask: usize) {
+    self.1 = mask;
+  }
+
+  pub const fn set_mask_offset(&mut self, mask_offset: usize) {
+    self.2 = mask_offset;
+  }
+
+  pub const fn set_bounds(&mut self, bounds: (usize, usize)) {
+    self.3 = bounds;
+  }
+
+  pub const fn mask_size(&self) -> usize {
     let VfhmParams(_, mask, mask_offset, _) = *self;
     (mask >> mask_offset) + 1
   }
 
+  /// The largest table [`VfhmParams::is_valid`] accepts, so params read from untrusted input
+  /// can't ask for an allocation the process can't make.
+  pub const MAX_MASK_SIZE: usize = 1 << 24;
+
+  pub const BYTES_LEN: usize = 5 * mem::size_of::<u64>();
+
+  /// Seed, mask, mask offset and both bounds as little-endian `u64`s, independent of the target's
+  /// pointer width.
+  pub fn to_bytes(&self) -> [u8; Self::BYTES_LEN] {
+    let VfhmParams(seed, mask, mask_offset, (lower, upper)) = *self;
+    let mut bytes = [0; Self::BYTES_LEN];
+
+    for (chunk, field) in
+      bytes
+        .chunks_exact_mut(mem::size_of::<u64>())
+        .zip([seed, mask, mask_offset, lower, upper])
+    {
+      chunk.copy_from_slice(&(field as u64).to_le_bytes());
+    }
+
+    bytes
+  }
+
+  /// Returns `None` when the length is wrong, a field doesn't fit in `usize` or the params aren't
+  /// [valid](VfhmParams::is_valid).
+  pub fn from_bytes(bytes: &[u8]) -> Option<VfhmParams> {
+    if bytes.len() != Self::BYTES_LEN {
+      return None;
+    }
+
+    let mut fields = bytes
+      .chunks_exact(mem::size_of::<u64>())
+      .map(|chunk| usize::try_from(u64::from_le_bytes(chunk.try_into().ok()?)).ok());
+    let mut next = || fields.next().flatten();
+
+    let params = VfhmParams(next()?, next()?, next()?, (next()?, next()?));
+
+    params.is_valid().then_some(params)
+  }
+
   pub fn bounds_mut(&mut self) -> &mut (usize, usize) {
     &mut self.3
   }
 
   pub fn bound_check<K>(&self, key: &K) -> bool
   where
-    K: VfhmKey,
+    K: VfhmKey + ?Sized,
   {
+    self.len_in_bounds(key.key_len())
+  }
+
+  /// Whether a table can be built for these params: the mask offset is within the bit width, the
+  /// mask size is at most [`VfhmParams::MAX_MASK_SIZE`] and the bounds aren't inverted.
+  pub const fn is_valid(&self) -> bool {
+    let VfhmParams(_, mask, mask_offset, (lower, upper)) = *self;
+
+    mask_offset < usize::BITS as usize
+      && mask >> mask_offset < Self::MAX_MASK_SIZE
+      && lower <= upper
+  }
+
+  pub const fn len_in_bounds(&self, len: usize) -> bool {
     let (lower, upper) = self.3;
-    let len = key.key_len();
 
     lower <= len && len <= upper
   }
+
+  /// Returns the hash before masking alongside the slot it lands in, for debugging collisions.
+  /// The raw value is the key hashed with a full, unshifted mask.
+  pub fn explain_key<K>(&self, key: &K) -> (usize, usize)
+  where
+    K: VfhmKey + ?Sized,
+  {
+    let raw = key.table_key(VfhmParams(self.0, usize::MAX, 0, self.3));
+
+    (raw, key.table_key(*self))
+  }
+
+  /// The candidate after `self` in the order documented on `VfhmBuilder::try_find_params`.
+  /// Returns `None` once the mask can't widen without going past [`VfhmParams::MAX_MASK_SIZE`].
+  #[cfg(feature = "builder")]
+  pub(crate) fn next_candidate(self, first_seed: usize) -> Option<VfhmParams> {
+    let VfhmParams(mut seed, mut mask, mut mask_offset, bounds) = self;
+
+    if seed == first_seed.wrapping_add(mask >> mask_offset) {
+      seed = first_seed;
+
+      if mask_offset + (1 + (mask >> mask_offset)).isqrt() + 1 < mem::size_of::<usize>() * 8 {
+        mask <<= 1;
+        mask_offset += 1;
+      } else if self.mask_size() < Self::MAX_MASK_SIZE {
+        mask = ((mask >> mask_offset) << 1) | 1;
+        mask_offset = 0;
+      } else {
+        return None;
+      }
+    } else {
+      seed = seed.wrapping_add(1);
+    }
+
+    Some(VfhmParams(seed, mask, mask_offset, bounds))
+  }
 }
 
+/// Borrowed forms used for lookups (`str` for `String`) must hash and compare the same as the
+/// owned key.
 pub trait VfhmKey {
   fn key_len(&self) -> usize;
 
@@ -144,24 +808,19 @@ pub trait VfhmKey {
 
 impl<T> VfhmKey for T
 where
-  T: AsRef<[u8]>,
+  T: AsRef<[u8]> + ?Sized,
 {
   #[inline]
   fn key_len(&self) -> usize {
     self.as_ref().len()
   }
 
-  fn table_key(&self, VfhmParams(seed, mask, mask_offset, _): VfhmParams) -> usize {
-    let mut index: usize = 1;
+  #[inline(always)]
+  fn table_key(&self, params: VfhmParams) -> usize {
+    hash_bytes::<Multiplicative>(self.as_ref(), params)
+  }
 
-    for byte in self.as_ref() {
-      index = index.wrapping_mul(*byte as usize).wrapping_sub(seed)
-    }
-
-    (index & mask) >> mask_offset
-  }
-
-  #[inline]
+  #[inline(always)]
   fn table_key_compare(&self, other: &Self) -> bool {
     self.as_ref() == other.as_ref()
   }
@@ -170,20 +829,17 @@ where
 #[cfg(test)]
 mod tests {
 
+  use vfhm_derive::VfhmStaticMap;
+
   use crate::{
-    builder::VfhmBuilder,
-    r#static::{StaticVfhm, VfhmStaticMap},
+    array::ArrayVfhm, builder::VfhmBuilder, r#static::StaticVfhm, set::VfhmSet, Vfhm, VfhmError,
+    VfhmParams,
   };
 
+  #[derive(VfhmStaticMap)]
+  #[vfhm(seed = 1, mask = 112, mask_offset = 4, bounds = (6, 9))]
   struct DaysParams;
 
-  impl VfhmStaticMap for DaysParams {
-    const SEED: usize = 1;
-    const MASK: usize = 112;
-    const MASK_OFFSET: usize = 4;
-    const BONDS: (usize, usize) = (6, 9);
-  }
-
   type DaysMap<K, V> = StaticVfhm<K, V, DaysParams>;
 
   #[test]
@@ -238,4 +894,1066 @@ mod tests {
     assert_eq!(hashmap.get("firday"), Some(&6));
     assert_eq!(hashmap.get("saturday"), Some(&7));
   }
+
+  #[test]
+  fn remove_colliding_key() {
+    let mut hashmap = Vfhm::with_params(VfhmParams(0, 0, 0, (0, usize::MAX)));
+
+    hashmap.insert("sunday", 1);
+
+    assert_eq!(hashmap.remove("monday"), None);
+    assert_eq!(hashmap.len(), 1);
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+
+    assert_eq!(hashmap.remove("sunday"), Some(1));
+    assert!(hashmap.is_empty());
+  }
+
+  #[test]
+  fn remove_borrowed() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+
+    let key = String::from("monday");
+
+    assert_eq!(hashmap.remove(key.as_str()), Some(2));
+    assert_eq!(hashmap.get("monday"), None);
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+    assert_eq!(hashmap.len(), 1);
+  }
+
+  #[test]
+  fn get_mut() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+
+    if let Some(value) = hashmap.get_mut("sunday") {
+      *value += 1;
+    }
+
+    assert_eq!(hashmap.get("sunday"), Some(&2));
+    assert_eq!(hashmap.get_mut("monday"), None);
+  }
+
+  #[test]
+  fn iter() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+
+    for (_, value) in hashmap.iter_mut() {
+      *value *= 10;
+    }
+
+    let mut entries: Vec<_> = hashmap.iter().collect();
+    entries.sort();
+
+    assert_eq!(
+      entries,
+      vec![(&"monday", &20), (&"sunday", &10), (&"tuesday", &30)]
+    );
+  }
+
+  #[test]
+  fn into_iter() {
+    let mut hashmap = Vfhm::with_params(DaysParams);
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+
+    let mut borrowed: Vec<_> = (&hashmap).into_iter().collect();
+    borrowed.sort();
+
+    assert_eq!(
+      borrowed,
+      vec![(&"monday", &2), (&"sunday", &1), (&"tuesday", &3)]
+    );
+
+    let mut owned: Vec<_> = hashmap.into_iter().collect();
+    owned.sort();
+
+    assert_eq!(owned, vec![("monday", 2), ("sunday", 1), ("tuesday", 3)]);
+  }
+
+  #[test]
+  fn keys_values() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+
+    for value in hashmap.values_mut() {
+      *value += 1;
+    }
+
+    let mut keys: Vec<_> = hashmap.keys().collect();
+    keys.sort();
+
+    assert_eq!(keys.len(), hashmap.len());
+    assert_eq!(keys, vec![&"monday", &"sunday", &"tuesday"]);
+    assert_eq!(hashmap.values().count(), hashmap.len());
+    assert_eq!(hashmap.values().sum::<i32>(), 9);
+  }
+
+  #[test]
+  fn try_insert() {
+    let mut hashmap = Vfhm::with_params(VfhmParams(0, 0, 0, (0, usize::MAX)));
+
+    assert_eq!(hashmap.try_insert("sunday", 1), Ok(None));
+    assert_eq!(hashmap.try_insert("sunday", 2), Ok(Some(("sunday", 1))));
+    assert_eq!(hashmap.try_insert("monday", 3), Err(("monday", 3)));
+
+    assert_eq!(hashmap.get("sunday"), Some(&2));
+    assert_eq!(hashmap.len(), 1);
+
+    let mut hashmap = Vfhm::with_params(DaysParams);
+
+    assert_eq!(hashmap.try_insert("holidays!!", 1), Err(("holidays!!", 1)));
+    assert!(hashmap.is_empty());
+  }
+
+  #[test]
+  fn try_find_params_exhausted() {
+    let result = VfhmBuilder::<_, ()>::default()
+      .set_keys(vec!["a\0", "b\0"])
+      .try_find_params(100)
+      .map(|builder| builder.build());
+
+    assert_eq!(
+      result.unwrap_err(),
+      VfhmError::SearchExhausted {
+        iterations: 100,
+        best_collisions: 1,
+        best_report: vec![(0, vec![0, 1])],
+      }
+    );
+  }
+
+  vfhm_derive::perfect_map! {
+    PerfectDays<&'static str, i32> {
+      "sunday" => 1,
+      "monday" => 2,
+      "tuesday" => 3,
+      "wednesday" => 4,
+      "thursday" => 5,
+      "firday" => 6,
+      "saturday" => 7,
+    }
+  }
+
+  #[test]
+  fn perfect_map() {
+    let mut hashmap = PerfectDays::new();
+
+    assert_eq!(hashmap.len(), 7);
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+    assert_eq!(hashmap.get("monday"), Some(&2));
+    assert_eq!(hashmap.get("tuesday"), Some(&3));
+    assert_eq!(hashmap.get("wednesday"), Some(&4));
+    assert_eq!(hashmap.get("thursday"), Some(&5));
+    assert_eq!(hashmap.get("firday"), Some(&6));
+    assert_eq!(hashmap.get("saturday"), Some(&7));
+    assert_eq!(hashmap.get("someday"), None);
+
+    hashmap.insert("sunday", 8);
+
+    assert_eq!(hashmap.get("sunday"), Some(&8));
+  }
+
+  #[test]
+  fn build_populated() {
+    let hashmap = VfhmBuilder::default()
+      .set_entries(vec![
+        ("sunday", 1),
+        ("monday", 2),
+        ("tuesday", 3),
+        ("wednesday", 4),
+        ("thursday", 5),
+        ("firday", 6),
+        ("saturday", 7),
+      ])
+      .find_params(1000)
+      .build_populated();
+
+    assert_eq!(hashmap.len(), 7);
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+    assert_eq!(hashmap.get("wednesday"), Some(&4));
+    assert_eq!(hashmap.get("saturday"), Some(&7));
+
+    let mut builder = VfhmBuilder::<&str, i32>::default();
+
+    assert_eq!(builder.build_populated().params, builder.build().params);
+  }
+
+  #[test]
+  fn add_key() {
+    let days = [
+      "sunday",
+      "monday",
+      "tuesday",
+      "wednesday",
+      "thursday",
+      "firday",
+      "saturday",
+    ];
+
+    let mut incremental = VfhmBuilder::<_, ()>::default();
+
+    for day in days {
+      incremental.add_key(day);
+    }
+
+    let mut batch = VfhmBuilder::<_, ()>::default();
+    batch.set_keys(days.to_vec());
+
+    assert_eq!(incremental.into_params().3, batch.into_params().3);
+  }
+
+  #[test]
+  fn build_without_keys() {
+    let mut builder = VfhmBuilder::<&str, i32>::default();
+
+    assert_eq!(
+      builder.try_find_params(1000).unwrap_err(),
+      VfhmError::NoKeys
+    );
+
+    let mut hashmap = builder.build();
+
+    hashmap.insert("", 1);
+
+    assert_eq!(hashmap.get(""), Some(&1));
+    assert_eq!(hashmap.get("sunday"), None);
+  }
+
+  #[test]
+  fn from_iter() {
+    let hashmap: Vfhm<_, _> = [
+      "sunday",
+      "monday",
+      "tuesday",
+      "wednesday",
+      "thursday",
+      "firday",
+      "saturday",
+    ]
+    .into_iter()
+    .zip(1..)
+    .collect();
+
+    assert_eq!(hashmap.len(), 7);
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+    assert_eq!(hashmap.get("monday"), Some(&2));
+    assert_eq!(hashmap.get("tuesday"), Some(&3));
+    assert_eq!(hashmap.get("wednesday"), Some(&4));
+    assert_eq!(hashmap.get("thursday"), Some(&5));
+    assert_eq!(hashmap.get("firday"), Some(&6));
+    assert_eq!(hashmap.get("saturday"), Some(&7));
+  }
+
+  #[test]
+  fn entry() {
+    let mut hashmap = DaysMap::new();
+
+    for word in "sunday monday sunday tuesday sunday monday".split(' ') {
+      *hashmap.entry(word).or_insert(0) += 1;
+    }
+
+    hashmap.entry("tuesday").and_modify(|count| *count *= 10);
+
+    assert_eq!(hashmap.len(), 3);
+    assert_eq!(hashmap.get("sunday"), Some(&3));
+    assert_eq!(hashmap.get("monday"), Some(&2));
+    assert_eq!(hashmap.get("tuesday"), Some(&10));
+  }
+
+  #[test]
+  fn clear_retain() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+    hashmap.insert("wednesday", 4);
+
+    hashmap.retain(|_, value| *value % 2 == 0);
+
+    assert_eq!(hashmap.len(), 2);
+    assert_eq!(hashmap.get("sunday"), None);
+    assert_eq!(hashmap.get("monday"), Some(&2));
+    assert_eq!(hashmap.get("tuesday"), None);
+    assert_eq!(hashmap.get("wednesday"), Some(&4));
+
+    hashmap.clear();
+
+    assert!(hashmap.is_empty());
+    assert_eq!(hashmap.get("monday"), None);
+  }
+
+  #[test]
+  fn get_key_value() {
+    let mut hashmap = Vfhm::with_params(DaysParams);
+
+    let stored = String::from("monday");
+    hashmap.insert(stored.as_str(), 2);
+
+    let (key, value) = hashmap.get_key_value("monday").unwrap();
+
+    assert_eq!(*key, "monday");
+    assert_eq!(key.as_ptr(), stored.as_ptr());
+    assert_eq!(value, &2);
+    assert_eq!(hashmap.get_key_value("sunday"), None);
+  }
+
+  #[test]
+  fn collision_report() {
+    let mut builder = VfhmBuilder::<_, ()>::default();
+    builder.set_keys(vec!["sunday", "monday", "tuesday"]);
+
+    let report = builder.collision_report(VfhmParams(0, 0, 0, (6, 7)));
+
+    assert_eq!(report, vec![(0, vec![0, 1, 2])]);
+
+    let report = builder.collision_report(DaysParams.into());
+
+    assert_eq!(report.len(), 3);
+    assert!(report.iter().all(|(_, keys)| keys.len() == 1));
+  }
+
+  #[test]
+  fn array() {
+    let mut hashmap = ArrayVfhm::<_, _, 8>::with_params(DaysParams);
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+    hashmap.insert("wednesday", 4);
+    hashmap.insert("thursday", 5);
+    hashmap.insert("firday", 6);
+    hashmap.insert("saturday", 7);
+
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+    assert_eq!(hashmap.get("monday"), Some(&2));
+    assert_eq!(hashmap.get("tuesday"), Some(&3));
+    assert_eq!(hashmap.get("wednesday"), Some(&4));
+    assert_eq!(hashmap.get("thursday"), Some(&5));
+    assert_eq!(hashmap.get("firday"), Some(&6));
+    assert_eq!(hashmap.get("saturday"), Some(&7));
+
+    assert_eq!(hashmap.remove("sunday"), Some(1));
+    assert_eq!(hashmap.len(), 6);
+  }
+
+  #[test]
+  #[should_panic]
+  fn array_size_mismatch() {
+    ArrayVfhm::<&str, i32, 4>::with_params(DaysParams);
+  }
+
+  vfhm_derive::perfect_map! {
+    Methods<&'static [u8], i32> {
+      b"GET" => 1,
+      b"POST" => 2,
+      b"PUT" => 3,
+    }
+  }
+
+  #[test]
+  fn perfect_map_bytes() {
+    let hashmap = Methods::new();
+
+    let input: &[u8] = b"POST /index.html";
+
+    assert_eq!(hashmap.get(&input[..4]), Some(&2));
+    assert_eq!(hashmap.get(&input[..3]), None);
+    assert_eq!(hashmap.get(&b"GET"[..]), Some(&1));
+  }
+
+  #[test]
+  fn index() {
+    let mut hashmap = Vfhm::with_params(DaysParams);
+
+    hashmap.insert("monday", 2);
+    hashmap["monday"] += 1;
+
+    assert_eq!(hashmap["monday"], 3);
+  }
+
+  #[test]
+  #[should_panic(expected = "key is not present in Vfhm")]
+  fn index_missing() {
+    let hashmap = Vfhm::<&str, i32>::with_params(DaysParams);
+
+    let _ = hashmap["monday"];
+  }
+
+  #[test]
+  fn extend() {
+    let mut hashmap = Vfhm::with_params(DaysParams);
+
+    hashmap.extend(vec![
+      ("sunday", 1),
+      ("monday", 2),
+      ("tuesday", 3),
+      ("wednesday", 4),
+      ("thursday", 5),
+      ("firday", 6),
+      ("saturday", 7),
+    ]);
+
+    assert_eq!(hashmap.len(), 7);
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+    assert_eq!(hashmap.get("monday"), Some(&2));
+    assert_eq!(hashmap.get("tuesday"), Some(&3));
+    assert_eq!(hashmap.get("wednesday"), Some(&4));
+    assert_eq!(hashmap.get("thursday"), Some(&5));
+    assert_eq!(hashmap.get("firday"), Some(&6));
+    assert_eq!(hashmap.get("saturday"), Some(&7));
+  }
+
+  #[test]
+  fn params_accessors() {
+    let mut params = VfhmParams::new(1, 112, 4, (6, 9));
+
+    assert_eq!(params, VfhmParams(1, 112, 4, (6, 9)));
+    assert_eq!(params.seed(), params.0);
+    assert_eq!(params.mask(), params.1);
+    assert_eq!(params.mask_offset(), params.2);
+    assert_eq!(params.bounds(), params.3);
+
+    params.set_seed(2);
+    params.set_mask(224);
+    params.set_mask_offset(5);
+    params.set_bounds((1, 10));
+
+    assert_eq!(params, VfhmParams(2, 224, 5, (1, 10)));
+  }
+
+  #[test]
+  fn static_with_entries() {
+    let hashmap = DaysMap::with_entries([("sunday", 1), ("monday", 2), ("tuesday", 3)]);
+
+    assert_eq!(hashmap.len(), 3);
+    assert_eq!(hashmap.get("monday"), Some(&2));
+  }
+
+  #[test]
+  #[should_panic(expected = "key collides with another key")]
+  fn static_with_entries_collision() {
+    #[derive(VfhmStaticMap)]
+    #[vfhm(seed = 0, mask = 0, mask_offset = 0, bounds = (6, 9))]
+    struct BrokenParams;
+
+    StaticVfhm::<_, _, BrokenParams>::with_entries([("sunday", 1), ("monday", 2)]);
+  }
+
+  #[test]
+  #[should_panic(expected = "key is outside of the static map bounds")]
+  fn static_with_entries_out_of_bounds() {
+    DaysMap::with_entries([("christmas day", 1)]);
+  }
+
+  #[test]
+  fn with_capacity() {
+    for capacity in [0, 1, 7, 8, 46, 1000] {
+      let hashmap = Vfhm::<&str, i32>::with_capacity(capacity);
+
+      assert!(hashmap.params.mask_size() >= 2 * capacity);
+    }
+
+    assert_eq!(VfhmParams::with_capacity(5).mask_size(), 16);
+
+    for capacity in [VfhmParams::MAX_MASK_SIZE, usize::MAX / 2 + 1, usize::MAX] {
+      let params = VfhmParams::with_capacity(capacity);
+
+      assert_eq!(params.mask_size(), VfhmParams::MAX_MASK_SIZE);
+      assert!(params.is_valid());
+    }
+  }
+
+  #[test]
+  fn eq() {
+    let days = [
+      ("sunday", 1),
+      ("monday", 2),
+      ("tuesday", 3),
+      ("wednesday", 4),
+      ("thursday", 5),
+      ("firday", 6),
+      ("saturday", 7),
+    ];
+
+    let mut built = VfhmBuilder::default()
+      .set_keys(vec![
+        "sunday",
+        "monday",
+        "tuesday",
+        "wednesday",
+        "thursday",
+        "firday",
+        "saturday",
+        "weekend",
+        "holiday",
+      ])
+      .find_params(100_000)
+      .build();
+    built.extend(days);
+
+    let mut fixed = DaysMap::with_entries(days);
+
+    assert_ne!(built.params, fixed.params);
+    assert_eq!(built, *fixed);
+
+    fixed.insert("sunday", 8);
+
+    assert_ne!(built, *fixed);
+
+    fixed.remove("sunday");
+
+    assert_ne!(built, *fixed);
+  }
+
+  #[test]
+  fn eq_out_of_bounds_key() {
+    let mut hashmap = Vfhm::with_params(DaysParams);
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("holidays!!", 2);
+
+    assert_eq!(hashmap.get("holidays!!"), None);
+    assert_eq!(hashmap, hashmap.clone());
+  }
+
+  #[test]
+  fn map_values() {
+    let mut hashmap = Vfhm::with_params(DaysParams);
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+
+    hashmap.values_mut().for_each(|value| *value *= 10);
+
+    let hashmap = hashmap.map_values(|value| value.to_string());
+
+    assert_eq!(hashmap.len(), 2);
+    assert_eq!(hashmap.get("sunday"), Some(&"10".to_string()));
+    assert_eq!(hashmap.get("monday"), Some(&"20".to_string()));
+  }
+
+  #[test]
+  fn drain() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+
+    let mut drained: Vec<_> = hashmap.drain().collect();
+    drained.sort();
+
+    assert_eq!(drained, vec![("monday", 2), ("sunday", 1), ("tuesday", 3)]);
+    assert!(hashmap.is_empty());
+    assert_eq!(hashmap.get("monday"), None);
+
+    hashmap.insert("friday", 6);
+    hashmap.insert("saturday", 7);
+    hashmap.drain().next();
+
+    assert!(hashmap.is_empty());
+  }
+
+  #[test]
+  fn find_key() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+    hashmap.insert("wednesday", 4);
+    hashmap.insert("thursday", 5);
+
+    assert_eq!(hashmap.find_key(|value| *value == 4), Some(&"wednesday"));
+    assert_eq!(hashmap.find_key(|value| *value == 8), None);
+  }
+
+  #[test]
+  fn memory_usage() {
+    let small = Vfhm::<&str, usize>::with_params(VfhmParams(0, 0b111, 0, (0, 10)));
+    let large = Vfhm::<&str, usize>::with_params(VfhmParams(0, 0b111111, 0, (0, 10)));
+
+    let slot = core::mem::size_of::<Option<(&str, usize)>>();
+
+    assert!(small.memory_usage() >= 8 * slot);
+    assert_eq!(large.memory_usage() - small.memory_usage(), (64 - 8) * slot);
+  }
+
+  #[test]
+  fn load_factor() {
+    let mut hashmap = DaysMap::new();
+
+    assert_eq!(hashmap.load_factor(), 0.0);
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+    hashmap.insert("wednesday", 4);
+    hashmap.insert("thursday", 5);
+    hashmap.insert("firday", 6);
+    hashmap.insert("saturday", 7);
+
+    assert!(hashmap.load_factor() > 0.0 && hashmap.load_factor() <= 1.0);
+    assert_eq!(hashmap.load_factor(), 7.0 / 8.0);
+  }
+
+  #[test]
+  fn contains_value() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+    hashmap.insert("wednesday", 4);
+
+    assert!(hashmap.contains_value(&4));
+    assert!(!hashmap.contains_value(&99));
+  }
+
+  #[test]
+  fn get_disjoint_mut() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+
+    if let Some([sunday, tuesday]) = hashmap.get_disjoint_mut(["sunday", "tuesday"]) {
+      *sunday += 10;
+      *tuesday += 10;
+    }
+
+    assert_eq!(hashmap.get("sunday"), Some(&11));
+    assert_eq!(hashmap.get("tuesday"), Some(&13));
+
+    assert!(hashmap.get_disjoint_mut(["monday", "friday"]).is_none());
+    assert!(hashmap.get_disjoint_mut(["monday", "monday"]).is_none());
+  }
+
+  #[test]
+  fn default() {
+    let hashmap = Vfhm::<&str, i32>::default();
+
+    assert!(hashmap.is_empty());
+    assert!(!hashmap.contains_key(""));
+  }
+
+  #[test]
+  fn borrowed_lookup() {
+    let mut hashmap: Vfhm<String, i32> = VfhmBuilder::default()
+      .set_keys(vec!["sunday".to_string(), "monday".to_string()])
+      .find_params(1000)
+      .build();
+
+    hashmap.insert("sunday".to_string(), 1);
+    hashmap.insert("monday".to_string(), 2);
+
+    assert_eq!(hashmap.get("monday"), Some(&2));
+    assert_eq!(hashmap.get(&"monday".to_string()), Some(&2));
+    assert_eq!(hashmap["sunday"], 1);
+    assert!(!hashmap.contains_key("friday"));
+    assert_eq!(
+      hashmap.remove_entry("sunday"),
+      Some(("sunday".to_string(), 1))
+    );
+  }
+
+  #[test]
+  fn params_bytes_round_trip() {
+    for params in [
+      VfhmParams(0, 1, 0, (usize::MAX, usize::MAX)),
+      VfhmParams(1, 112, 4, (6, 9)),
+      VfhmParams(12_345, 0xffff << 40, 40, (0, usize::MAX)),
+      VfhmParams::with_capacity(10_000),
+    ] {
+      assert_eq!(VfhmParams::from_bytes(&params.to_bytes()), Some(params));
+    }
+
+    assert_eq!(VfhmParams::from_bytes(&[0; 8]), None);
+
+    for params in [
+      VfhmParams(0, 1, 200, (0, 10)),
+      VfhmParams(0, 1, usize::BITS as usize, (0, 10)),
+      VfhmParams(0, usize::MAX, 0, (0, 10)),
+      VfhmParams(0, usize::MAX << 7, 7, (0, 10)),
+      VfhmParams(0, VfhmParams::MAX_MASK_SIZE, 0, (0, 10)),
+      VfhmParams(0, 1, 0, (9, 6)),
+    ] {
+      assert_eq!(VfhmParams::from_bytes(&params.to_bytes()), None);
+    }
+  }
+
+  #[test]
+  fn next_candidate_widens() {
+    let bounds = (0, 10);
+
+    // Past the last seed of an unshifted mask too wide to slide.
+    assert_eq!(
+      VfhmParams(4095, 4095, 0, bounds).next_candidate(0),
+      Some(VfhmParams(0, 8191, 0, bounds))
+    );
+
+    let last = VfhmParams::MAX_MASK_SIZE - 1;
+    assert_eq!(VfhmParams(last, last, 0, bounds).next_candidate(0), None);
+  }
+
+  #[test]
+  fn const_params() {
+    use crate::r#static::VfhmStaticMap;
+
+    const N: usize = DaysParams::PARAMS.mask_size();
+    const CAPACITY: VfhmParams = VfhmParams::with_capacity(10);
+
+    let mut hashmap = ArrayVfhm::<_, _, N>::with_params(DaysParams);
+
+    hashmap.insert("sunday", 1);
+
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+    assert_eq!(CAPACITY.mask_size(), 32);
+    const { assert!(DaysParams::PARAMS.len_in_bounds(6)) };
+  }
+
+  #[test]
+  fn get_unchecked() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+
+    assert_eq!(hashmap.get_unchecked("sunday"), Some(&1));
+    assert_eq!(hashmap.get_unchecked("monday"), Some(&2));
+    assert_eq!(hashmap.get_unchecked("tuesday"), None);
+    assert_eq!(
+      hashmap.get_unchecked("a much longer word than the bounds"),
+      None
+    );
+  }
+
+  #[test]
+  fn scan() {
+    let hashmap = Vfhm::from([("if", 1), ("return", 2), ("new", 3)]);
+
+    let tokens: Vec<_> = hashmap.scan("if (ready) return new Widget(42);").collect();
+
+    assert_eq!(
+      tokens,
+      vec![
+        ("if", Some(&1)),
+        ("ready", None),
+        ("return", Some(&2)),
+        ("new", Some(&3)),
+        ("Widget", None),
+      ]
+    );
+  }
+
+  #[test]
+  fn static_from_keys() {
+    let days = [
+      "sunday",
+      "monday",
+      "tuesday",
+      "wednesday",
+      "thursday",
+      "firday",
+      "saturday",
+    ];
+
+    let hashmap = DaysMap::from_keys(&days);
+
+    for (index, day) in days.iter().enumerate() {
+      assert_eq!(hashmap.get(day), Some(&index));
+    }
+
+    assert_eq!(hashmap.get("holiday"), None);
+  }
+
+  #[test]
+  fn invalid_bounds() {
+    let mut builder = VfhmBuilder::<_, ()>::default();
+    builder.set_keys(vec!["sunday"]).set_bounds((9, 6));
+
+    assert_eq!(
+      builder.try_build().unwrap_err(),
+      VfhmError::InvalidBounds { lower: 9, upper: 6 }
+    );
+  }
+
+  #[test]
+  fn debug_hides_empty_slots() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("monday", 2);
+
+    let debug = format!("{:?}", *hashmap);
+
+    assert!(!debug.contains("None"));
+    assert!(debug.contains(r#"entries: {"monday": 2}"#));
+    assert!(debug.contains("mask_size: 8"));
+  }
+
+  #[test]
+  fn capacity() {
+    let mut hashmap = DaysMap::new();
+
+    assert_eq!(hashmap.capacity(), 8);
+
+    for (value, day) in (1..).zip(["sunday", "monday", "tuesday", "wednesday"]) {
+      hashmap.insert(day, value);
+
+      assert!(hashmap.capacity() >= hashmap.len());
+    }
+  }
+
+  #[test]
+  fn set() {
+    let days = [
+      "sunday",
+      "monday",
+      "tuesday",
+      "wednesday",
+      "thursday",
+      "firday",
+      "saturday",
+    ];
+
+    let mut builder = VfhmBuilder::default();
+    let set = builder
+      .set_keys(days.to_vec())
+      .find_params(1000)
+      .build_set();
+
+    assert_eq!(set.len(), 7);
+    assert!(days.iter().all(|day| set.contains(day)));
+    assert!(!set.contains("holiday"));
+
+    let mut set = VfhmBuilder::<&str, ()>::default().build_set();
+
+    assert!(set.insert(""));
+    assert!(set.contains(""));
+    assert!(!set.contains("holiday"));
+
+    let mut set = VfhmSet::with_params(DaysParams);
+
+    assert!(set.insert("monday"));
+    assert!(!set.insert("monday"));
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![&"monday"]);
+  }
+
+  #[test]
+  fn increment() {
+    let mut counts = VfhmBuilder::<_, u32>::default()
+      .set_keys(vec!["sunday", "monday", "friday"])
+      .find_params(1000)
+      .build();
+
+    "monday again, then friday, then monday and another monday"
+      .split(|c: char| !c.is_alphabetic())
+      .filter(|word| word.ends_with("day"))
+      .for_each(|word| {
+        counts.increment(word);
+      });
+
+    assert_eq!(counts.get("monday"), Some(&3));
+    assert_eq!(counts.get("friday"), Some(&1));
+    assert_eq!(counts.get("sunday"), None);
+    assert_eq!(*counts.increment("sunday"), 1);
+  }
+
+  #[test]
+  fn recompute_bounds() {
+    let mut hashmap = VfhmBuilder::default()
+      .set_entries(vec![
+        ("do", 1),
+        ("if", 2),
+        ("return", 3),
+        ("typeof", 4),
+        ("function", 5),
+      ])
+      .find_params(1000)
+      .build_populated();
+
+    assert_eq!(hashmap.params.bounds(), (2, 8));
+
+    hashmap.remove("do");
+    hashmap.remove("if");
+    hashmap.remove("function");
+    hashmap.recompute_bounds();
+
+    assert_eq!(hashmap.params.bounds(), (6, 6));
+    assert!(!hashmap.params.bound_check("do"));
+    assert!(!hashmap.params.bound_check("function"));
+    assert_eq!(hashmap.get("return"), Some(&3));
+    assert_eq!(hashmap.get("typeof"), Some(&4));
+
+    hashmap.remove("return");
+    hashmap.remove("typeof");
+    hashmap.recompute_bounds();
+
+    assert!(hashmap.params.is_valid());
+    assert!(!hashmap.params.bound_check(""));
+    assert!(!hashmap.params.bound_check("return"));
+  }
+
+  #[test]
+  fn explain_key() {
+    let params = VfhmParams(0, 0b11, 0, (0, 10));
+    let explained =
+      ["do", "if", "in", "for", "new", "try", "var"].map(|key| params.explain_key(key));
+
+    for (raw, masked) in explained {
+      assert_eq!(masked, raw & 0b11);
+    }
+
+    // Seven keys over four slots, so at least two of them collide.
+    let (first, second) = (0..explained.len())
+      .flat_map(|first| (first + 1..explained.len()).map(move |second| (first, second)))
+      .map(|(first, second)| (explained[first], explained[second]))
+      .find(|(first, second)| first.1 == second.1)
+      .unwrap();
+
+    assert_ne!(first.0, second.0);
+  }
+
+  #[test]
+  fn to_sorted_vec() {
+    let days = [
+      ("sunday", 1),
+      ("monday", 2),
+      ("tuesday", 3),
+      ("wednesday", 4),
+    ];
+
+    let mut fixed = Vfhm::with_params(DaysParams);
+    fixed.extend(days);
+
+    let mut searched = VfhmBuilder::default()
+      .set_keys(days.map(|(day, _)| day).to_vec())
+      .search_seed(1_000)
+      .find_params(100_000)
+      .build();
+    searched.extend(days);
+
+    assert_ne!(searched.params, fixed.params);
+    assert_eq!(fixed.to_sorted_vec(), searched.to_sorted_vec());
+    assert_eq!(
+      searched.to_sorted_vec(),
+      vec![
+        (&"monday", &2),
+        (&"sunday", &1),
+        (&"tuesday", &3),
+        (&"wednesday", &4)
+      ]
+    );
+  }
+
+  #[test]
+  fn get_batch() {
+    let mut hashmap = DaysMap::new();
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+
+    let keys = [
+      "tuesday", "friday", "sunday", "sunday", "holiday", "", "monday",
+    ];
+
+    assert_eq!(hashmap.get_batch(keys), keys.map(|key| hashmap.get(key)));
+    assert_eq!(hashmap.get_batch(["monday", "weekend"]), [Some(&2), None]);
+  }
+
+  #[test]
+  fn static_into_inner() {
+    let mut hashmap = DaysMap::new();
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+
+    let inner: Vfhm<_, _> = hashmap.into_inner();
+
+    assert_eq!(inner.params, VfhmParams::from(DaysParams));
+    assert_eq!(inner.get("monday"), Some(&2));
+
+    let hashmap = DaysMap::try_from_vfhm(inner).unwrap();
+
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+    assert_eq!(hashmap.len(), 2);
+
+    let other = Vfhm::<&str, i32>::with_params(VfhmParams(2, 112, 4, (6, 9)));
+
+    assert!(DaysMap::try_from_vfhm(other).is_err_and(|map| map.params.seed() == 2));
+  }
+
+  #[test]
+  fn remove_entry() {
+    let mut hashmap = DaysMap::new();
+
+    let monday = String::from("monday");
+
+    hashmap.insert(monday.as_str(), 2);
+    hashmap.insert("sunday", 1);
+
+    let (key, value) = hashmap.remove_entry("monday").unwrap();
+
+    assert!(core::ptr::eq(key, monday.as_str()));
+    assert_eq!(value, 2);
+    assert_eq!(hashmap.remove_entry("monday"), None);
+    assert_eq!(hashmap.remove("sunday"), Some(1));
+    assert!(hashmap.is_empty());
+  }
+
+  #[test]
+  fn get_copied() {
+    const WEEKDAYS: &[u8] = &[1, 2, 3, 4, 5];
+
+    let descriptions: Vfhm<&str, &'static str> =
+      Vfhm::from([("if", "conditional"), ("for", "loop"), ("while", "loop")]);
+
+    assert_eq!(descriptions.get_copied("for"), Some("loop"));
+    assert_eq!(descriptions.get_copied("else"), None);
+
+    let mut slices: Vfhm<&str, &[u8]> = Vfhm::with_params(DaysParams);
+    slices.insert("weekdays", WEEKDAYS);
+    slices.insert("sunday", &WEEKDAYS[..0]);
+
+    assert_eq!(slices.get_copied("weekdays"), Some(WEEKDAYS));
+    assert_eq!(slices.get_copied("sunday"), Some(&[][..]));
+  }
+
+  #[cfg(feature = "rayon")]
+  #[test]
+  fn par_extend() {
+    use crate::scheme::IntKey;
+
+    let entries: Vec<_> = (0..10_000usize).map(|i| (IntKey(i), i)).collect();
+
+    let mut serial = Vfhm::with_params(VfhmParams::with_capacity(entries.len()));
+    serial.extend(entries.clone());
+
+    let mut parallel = Vfhm::with_params(VfhmParams::with_capacity(entries.len()));
+    parallel.par_extend(entries.clone());
+
+    assert!(parallel.len() > entries.len() / 2);
+    assert_eq!(parallel.len(), serial.len());
+    assert_eq!(parallel, serial);
+
+    for (IntKey(key), value) in parallel.iter() {
+      assert_eq!(key, value);
+    }
+  }
 }
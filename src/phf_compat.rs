@@ -0,0 +1,85 @@
+use crate::{Vfhm, VfhmKey};
+
+/// Runs the params search over the keys of the phf map, panics like [`FromIterator`] when no
+/// conflictless params are found.
+impl<K, V> From<&phf::Map<K, V>> for Vfhm<K, V>
+where
+  K: VfhmKey + Clone + 'static,
+  V: Clone + 'static,
+{
+  fn from(map: &phf::Map<K, V>) -> Self {
+    map
+      .entries()
+      .map(|(key, value)| (key.clone(), value.clone()))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::Vfhm;
+
+  static KEYWORDS: phf::Map<&'static str, i32> = phf::phf_map! {
+    "await" => 1,
+    "break" => 2,
+    "case" => 3,
+    "catch" => 4,
+    "class" => 5,
+    "const" => 6,
+    "continue" => 7,
+    "debugger" => 8,
+    "default" => 9,
+    "delete" => 10,
+    "do" => 11,
+    "else" => 12,
+    "enum" => 13,
+    "export" => 14,
+    "extends" => 15,
+    "false" => 16,
+    "finally" => 17,
+    "for" => 18,
+    "function" => 19,
+    "if" => 20,
+    "implements" => 21,
+    "import" => 22,
+    "in" => 23,
+    "instanceof" => 24,
+    "interface" => 25,
+    "let" => 26,
+    "new" => 27,
+    "null" => 28,
+    "package" => 29,
+    "private" => 30,
+    "protected" => 31,
+    "public" => 32,
+    "return" => 33,
+    "super" => 34,
+    "switch" => 35,
+    "static" => 36,
+    "this" => 37,
+    "throw" => 38,
+    "try" => 39,
+    "true" => 40,
+    "typeof" => 41,
+    "var" => 42,
+    "void" => 43,
+    "while" => 44,
+    "with" => 45,
+    "yield" => 46,
+  };
+
+  #[test]
+  fn from_phf_map() {
+    let hashmap = Vfhm::from(&KEYWORDS);
+
+    assert_eq!(hashmap.len(), KEYWORDS.len());
+
+    for (key, value) in KEYWORDS.entries() {
+      assert_eq!(hashmap.get(key), Some(value));
+    }
+
+    for word in ["jQuery", "fn", "each", "var_", "th"] {
+      assert_eq!(hashmap.get(word), KEYWORDS.get(word));
+    }
+  }
+}
@@ -0,0 +1,194 @@
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+
+use crate::{VfhmKey, VfhmParams};
+
+/// A `Vfhm` that doesn't rely on perfect params, colliding keys are moved to the next free slot
+/// (linear probing) and lookups probe the same way.
+#[derive(Debug, Clone)]
+pub struct ProbingVfhm<K, V> {
+  table: Vec<Option<(K, V)>>,
+  params: VfhmParams,
+  length: usize,
+}
+
+impl<K, V> ProbingVfhm<K, V> {
+  pub fn with_params<P>(maybe_params: P) -> Self
+  where
+    P: Into<VfhmParams>,
+  {
+    let params = maybe_params.into();
+
+    ProbingVfhm {
+      table: (0..params.mask_size()).map(|_| None).collect(),
+      params,
+      length: 0,
+    }
+  }
+}
+
+impl<K, V> ProbingVfhm<K, V>
+where
+  K: VfhmKey,
+{
+  pub fn len(&self) -> usize {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    self
+      .find(key)
+      .and_then(|index| self.table[index].as_ref())
+      .map(|(_, value)| value)
+  }
+
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    self
+      .find(key)
+      .and_then(|index| self.table[index].as_mut())
+      .map(|(_, value)| value)
+  }
+
+  /// Panics when every slot is taken by another key.
+  pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+    let size = self.table.len();
+    let home = key.table_key(self.params);
+
+    let index = (0..size)
+      .map(|offset| (home + offset) % size)
+      .find(|index| match &self.table[*index] {
+        Some((k, _)) => key.table_key_compare(k),
+        None => true,
+      })
+      .expect("ProbingVfhm is full");
+
+    let output = self.table[index].replace((key, value));
+
+    if output.is_none() {
+      self.length += 1;
+    }
+
+    output
+  }
+
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.remove_entry(key).map(|(_, value)| value)
+  }
+
+  /// Like [`ProbingVfhm::remove`] but also hands back the stored key.
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    let mut hole = self.find(key)?;
+    let output = self.table[hole].take();
+    let size = self.table.len();
+
+    self.length -= 1;
+
+    // Shift the following entries of the probe run back so lookups don't stop at the hole.
+    let mut index = (hole + 1) % size;
+
+    while let Some((k, _)) = &self.table[index] {
+      let home = k.table_key(self.params);
+
+      if (index + size - home) % size >= (index + size - hole) % size {
+        self.table[hole] = self.table[index].take();
+        hole = index;
+      }
+
+      index = (index + 1) % size;
+    }
+
+    output
+  }
+
+  fn find<Q>(&self, key: &Q) -> Option<usize>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    let size = self.table.len();
+    let home = key.table_key(self.params);
+
+    for offset in 0..size {
+      let index = (home + offset) % size;
+
+      match &self.table[index] {
+        Some((k, _)) if key.table_key_compare(k.borrow()) => return Some(index),
+        Some(_) => {}
+        None => return None,
+      }
+    }
+
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::ProbingVfhm;
+  use crate::VfhmParams;
+
+  #[test]
+  fn probing() {
+    let mut hashmap = ProbingVfhm::with_params(VfhmParams(0, 3, 0, (0, 10)));
+
+    hashmap.insert("a\0", 1);
+    hashmap.insert("b\0", 2);
+    hashmap.insert("c\0", 3);
+
+    assert_eq!(hashmap.len(), 3);
+    assert_eq!(hashmap.get("a\0"), Some(&1));
+    assert_eq!(hashmap.get("b\0"), Some(&2));
+    assert_eq!(hashmap.get("c\0"), Some(&3));
+    assert_eq!(hashmap.get("d\0"), None);
+
+    assert_eq!(hashmap.remove("a\0"), Some(1));
+    assert_eq!(hashmap.get("b\0"), Some(&2));
+    assert_eq!(hashmap.get("c\0"), Some(&3));
+
+    assert_eq!(hashmap.insert("c\0", 4), Some(("c\0", 3)));
+    assert_eq!(hashmap.len(), 2);
+
+    assert_eq!(hashmap.remove_entry("c\0"), Some(("c\0", 4)));
+    assert_eq!(hashmap.len(), 1);
+  }
+}
@@ -0,0 +1,152 @@
+use core::borrow::Borrow;
+
+use crate::{Vfhm, VfhmKey};
+
+/// 256-bit set of the bytes keys start with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FirstBytes([u64; 4]);
+
+impl FirstBytes {
+  pub const fn new() -> Self {
+    FirstBytes([0; 4])
+  }
+
+  pub const fn insert(&mut self, byte: u8) {
+    self.0[(byte >> 6) as usize] |= 1 << (byte & 63);
+  }
+
+  #[inline(always)]
+  pub const fn contains(&self, byte: u8) -> bool {
+    self.0[(byte >> 6) as usize] & (1 << (byte & 63)) != 0
+  }
+}
+
+/// [`Vfhm`] that rejects keys by their first byte before hashing, cheap for scans where most words
+/// miss.
+#[derive(Debug, Clone)]
+pub struct RoutedVfhm<K, V> {
+  inner: Vfhm<K, V>,
+  first_bytes: FirstBytes,
+  empty_key: bool,
+}
+
+impl<K, V> Vfhm<K, V>
+where
+  K: AsRef<[u8]>,
+{
+  pub fn route_first_byte(self) -> RoutedVfhm<K, V> {
+    let mut first_bytes = FirstBytes::new();
+    let mut empty_key = false;
+
+    for key in self.keys() {
+      match key.as_ref().first() {
+        Some(byte) => first_bytes.insert(*byte),
+        None => empty_key = true,
+      }
+    }
+
+    RoutedVfhm {
+      inner: self,
+      first_bytes,
+      empty_key,
+    }
+  }
+}
+
+impl<K, V> RoutedVfhm<K, V> {
+  pub fn first_bytes(&self) -> FirstBytes {
+    self.first_bytes
+  }
+
+  pub fn into_inner(self) -> Vfhm<K, V> {
+    self.inner
+  }
+}
+
+impl<K, V> RoutedVfhm<K, V>
+where
+  K: VfhmKey,
+{
+  pub fn len(&self) -> usize {
+    self.inner.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.inner.is_empty()
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + AsRef<[u8]> + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  #[inline(always)]
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + AsRef<[u8]> + ?Sized,
+  {
+    let routed = match key.as_ref().first() {
+      Some(byte) => self.first_bytes.contains(*byte),
+      None => self.empty_key,
+    };
+
+    if routed {
+      self.inner.get(key)
+    } else {
+      None
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::FirstBytes;
+  use crate::Vfhm;
+
+  #[test]
+  fn first_bytes() {
+    let mut first_bytes = FirstBytes::new();
+    first_bytes.insert(0);
+    first_bytes.insert(b's');
+    first_bytes.insert(255);
+
+    assert!(first_bytes.contains(0));
+    assert!(first_bytes.contains(b's'));
+    assert!(first_bytes.contains(255));
+    assert!(!first_bytes.contains(b'S'));
+    assert!(!first_bytes.contains(b't'));
+  }
+
+  #[test]
+  fn routes_by_first_byte() {
+    let routed = Vfhm::from([("sunday", 1), ("monday", 2), ("saturday", 6)]).route_first_byte();
+
+    assert_eq!(routed.get("sunday"), Some(&1));
+    assert_eq!(routed.get("monday"), Some(&2));
+    assert_eq!(routed.get("saturday"), Some(&6));
+
+    // Present first byte, still resolved by the map.
+    assert_eq!(routed.get("sundae"), None);
+    assert_eq!(routed.get("someday"), None);
+    // Absent first byte, rejected before hashing.
+    assert_eq!(routed.get("tuesday"), None);
+    assert_eq!(routed.get("Sunday"), None);
+    assert_eq!(routed.get(""), None);
+
+    assert!(routed.first_bytes().contains(b'm'));
+    assert!(!routed.first_bytes().contains(b't'));
+  }
+
+  #[test]
+  fn empty_key() {
+    let routed = Vfhm::from([("", 0), ("a", 1)]).route_first_byte();
+
+    assert_eq!(routed.get(""), Some(&0));
+    assert_eq!(routed.get("a"), Some(&1));
+    assert_eq!(routed.get("b"), None);
+  }
+}
@@ -0,0 +1,345 @@
+use core::{marker::PhantomData, mem};
+
+use crate::{VfhmKey, VfhmParams};
+
+pub trait HashScheme {
+  fn mix(index: usize, byte: u8, seed: usize) -> usize;
+
+  /// Mixes two consecutive bytes, must equal two [`HashScheme::mix`] calls.
+  #[inline]
+  fn mix_pair(index: usize, [first, second]: [u8; 2], seed: usize) -> usize {
+    Self::mix(Self::mix(index, first, seed), second, seed)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Multiplicative;
+
+impl HashScheme for Multiplicative {
+  #[inline]
+  fn mix(index: usize, byte: u8, seed: usize) -> usize {
+    index.wrapping_mul(byte as usize).wrapping_sub(seed)
+  }
+
+  #[inline]
+  fn mix_pair(index: usize, [first, second]: [u8; 2], seed: usize) -> usize {
+    // ((index * first - seed) * second - seed) with a single multiply on index.
+    index
+      .wrapping_mul(first as usize * second as usize)
+      .wrapping_sub(seed.wrapping_mul(second as usize + 1))
+  }
+}
+
+pub(crate) fn hash_bytes<H>(
+  bytes: &[u8],
+  VfhmParams(seed, mask, mask_offset, _): VfhmParams,
+) -> usize
+where
+  H: HashScheme,
+{
+  let mut index: usize = 1;
+  let mut pairs = bytes.chunks_exact(2);
+
+  for pair in &mut pairs {
+    index = H::mix_pair(index, [pair[0], pair[1]], seed);
+  }
+
+  if let [byte] = pairs.remainder() {
+    index = H::mix(index, *byte, seed);
+  }
+
+  (index & mask) >> mask_offset
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hashed<T, H = Multiplicative>(pub T, PhantomData<H>);
+
+impl<T, H> Hashed<T, H> {
+  pub fn new(key: T) -> Self {
+    Hashed(key, PhantomData)
+  }
+
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T, H> VfhmKey for Hashed<T, H>
+where
+  T: AsRef<[u8]>,
+  H: HashScheme,
+{
+  #[inline]
+  fn key_len(&self) -> usize {
+    self.0.as_ref().len()
+  }
+
+  fn table_key(&self, params: VfhmParams) -> usize {
+    hash_bytes::<H>(self.0.as_ref(), params)
+  }
+
+  #[inline]
+  fn table_key_compare(&self, other: &Self) -> bool {
+    self.0.as_ref() == other.0.as_ref()
+  }
+}
+
+/// Integer keys, hashed as a whole instead of through their bytes since the zero bytes of small
+/// integers would wipe out [`Multiplicative`]. The length is the fixed byte width, so bounds always
+/// hold for keys of a single type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntKey<T>(pub T);
+
+macro_rules! int_key {
+  ($($int:ty),*) => {
+    $(
+      impl VfhmKey for IntKey<$int> {
+        #[inline]
+        fn key_len(&self) -> usize {
+          mem::size_of::<$int>()
+        }
+
+        fn table_key(&self, params: VfhmParams) -> usize {
+          hash_int(self.0 as u64, params)
+        }
+
+        #[inline]
+        fn table_key_compare(&self, other: &Self) -> bool {
+          self.0 == other.0
+        }
+      }
+    )*
+  };
+}
+
+int_key!(u8, u16, u32, u64, usize);
+
+fn hash_int(value: u64, VfhmParams(seed, mask, mask_offset, _): VfhmParams) -> usize {
+  let hash = (value ^ seed as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+  let index = (hash ^ (hash >> 29)) as usize;
+
+  (index & mask) >> mask_offset
+}
+
+/// `OsStr`, `Path` and their owned forms as keys, hashed through their encoded bytes. The
+/// encoding is platform specific, so params found on one OS aren't valid on another.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OsKey<T>(pub T);
+
+#[cfg(feature = "std")]
+impl<T> VfhmKey for OsKey<T>
+where
+  T: AsRef<std::ffi::OsStr>,
+{
+  #[inline]
+  fn key_len(&self) -> usize {
+    self.0.as_ref().len()
+  }
+
+  fn table_key(&self, params: VfhmParams) -> usize {
+    hash_bytes::<Multiplicative>(self.0.as_ref().as_encoded_bytes(), params)
+  }
+
+  #[inline]
+  fn table_key_compare(&self, other: &Self) -> bool {
+    self.0.as_ref() == other.0.as_ref()
+  }
+}
+
+/// Any `AsRef<str>` as a key, hashing and bounding on its bytes like `&str` does. Covers
+/// `Cow<'_, str>`, which the blanket impl can't since it only implements `AsRef<str>`.
+/// `Cow<'_, [u8]>` needs no wrapper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrKey<T>(pub T);
+
+impl<T> VfhmKey for StrKey<T>
+where
+  T: AsRef<str>,
+{
+  #[inline]
+  fn key_len(&self) -> usize {
+    self.0.as_ref().len()
+  }
+
+  fn table_key(&self, params: VfhmParams) -> usize {
+    hash_bytes::<Multiplicative>(self.0.as_ref().as_bytes(), params)
+  }
+
+  #[inline]
+  fn table_key_compare(&self, other: &Self) -> bool {
+    self.0.as_ref() == other.0.as_ref()
+  }
+}
+
+/// String keys whose length is counted in `char`s rather than bytes, so bounds read as character
+/// counts for non-ASCII keywords. Hashing still goes over the bytes, but every bound check walks
+/// the whole key to count its characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharLenKey<T>(pub T);
+
+impl<T> VfhmKey for CharLenKey<T>
+where
+  T: AsRef<str>,
+{
+  #[inline]
+  fn key_len(&self) -> usize {
+    self.0.as_ref().chars().count()
+  }
+
+  fn table_key(&self, params: VfhmParams) -> usize {
+    hash_bytes::<Multiplicative>(self.0.as_ref().as_bytes(), params)
+  }
+
+  #[inline]
+  fn table_key_compare(&self, other: &Self) -> bool {
+    self.0.as_ref() == other.0.as_ref()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::borrow::Cow;
+
+  use super::{hash_bytes, CharLenKey, HashScheme, Hashed, IntKey, Multiplicative, StrKey};
+  use crate::{builder::VfhmBuilder, Vfhm, VfhmParams};
+
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+  struct Rotating;
+
+  impl HashScheme for Rotating {
+    fn mix(index: usize, byte: u8, seed: usize) -> usize {
+      index.rotate_left(5) ^ (byte as usize) ^ seed
+    }
+  }
+
+  #[test]
+  fn alternate_scheme() {
+    let mut default = VfhmBuilder::<_, ()>::default();
+
+    assert!(default
+      .set_keys(vec![Hashed::<_>::new("a\0"), Hashed::new("b\0")])
+      .try_find_params(1000)
+      .is_err());
+
+    let mut hashmap = VfhmBuilder::default()
+      .set_keys(vec![Hashed::<_, Rotating>::new("a\0"), Hashed::new("b\0")])
+      .find_params(1000)
+      .build();
+
+    hashmap.insert(Hashed::new("a\0"), 1);
+    hashmap.insert(Hashed::new("b\0"), 2);
+
+    assert_eq!(hashmap.get(&Hashed::new("a\0")), Some(&1));
+    assert_eq!(hashmap.get(&Hashed::new("b\0")), Some(&2));
+  }
+
+  #[test]
+  fn paired_mix_matches_scalar() {
+    for key in ["", "a", "do", "for", "case", "instanceof", "\u{ff}\u{fe}\0"] {
+      for seed in [0, 1, 7, usize::MAX] {
+        let scalar = key
+          .bytes()
+          .fold(1, |index, byte| Multiplicative::mix(index, byte, seed));
+
+        assert_eq!(
+          hash_bytes::<Multiplicative>(key.as_bytes(), VfhmParams(seed, usize::MAX, 0, (0, 10))),
+          scalar
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn int_keys() {
+    let keys = [10u32, 20, 30].map(IntKey);
+
+    let mut builder = VfhmBuilder::default();
+    let mut hashmap = builder.set_keys(keys.to_vec()).find_params(1000).build();
+
+    assert_eq!(builder.into_params().bounds(), (4, 4));
+
+    for (value, key) in (1..).zip(keys) {
+      hashmap.insert(key, value);
+    }
+
+    assert_eq!(hashmap.get(&IntKey(10)), Some(&1));
+    assert_eq!(hashmap.get(&IntKey(20)), Some(&2));
+    assert_eq!(hashmap.get(&IntKey(30)), Some(&3));
+    assert_eq!(hashmap.get(&IntKey(40)), None);
+  }
+
+  #[test]
+  fn cow_keys() {
+    let keys = vec![
+      StrKey(Cow::Borrowed("if")),
+      StrKey(Cow::Owned(String::from("match"))),
+      StrKey(Cow::Borrowed("return")),
+    ];
+
+    let mut builder = VfhmBuilder::default();
+    builder
+      .set_entries(keys.into_iter().zip(1..).collect())
+      .find_params(1000);
+
+    let hashmap = builder.build_populated();
+
+    assert_eq!(
+      hashmap.get(&StrKey(Cow::Owned(String::from("if")))),
+      Some(&1)
+    );
+    assert_eq!(hashmap.get(&StrKey(Cow::Borrowed("match"))), Some(&2));
+    assert_eq!(hashmap.get(&StrKey(Cow::Borrowed("else"))), None);
+
+    let bytes: Vfhm<Cow<[u8]>, i32> = Vfhm::from([
+      (Cow::Borrowed(&b"if"[..]), 1),
+      (Cow::Owned(b"match".to_vec()), 2),
+    ]);
+
+    assert_eq!(bytes.get(&b"match"[..]), Some(&2));
+  }
+
+  #[test]
+  fn char_len_keys() {
+    let keys = ["café", "naïve", "résumé"].map(CharLenKey);
+
+    let mut builder = VfhmBuilder::default();
+    builder
+      .set_entries(keys.iter().copied().zip(1..).collect())
+      .find_params(1000);
+
+    let hashmap = builder.build_populated();
+    let params = builder.into_params();
+
+    assert_eq!(params.bounds(), (4, 6));
+    // Six characters but eight bytes.
+    assert!(params.bound_check(&CharLenKey("résumé")));
+    assert!(!params.bound_check("résumé"));
+
+    assert_eq!(hashmap.get(&CharLenKey("café")), Some(&1));
+    assert_eq!(hashmap.get(&CharLenKey("naïve")), Some(&2));
+    assert_eq!(hashmap.get(&CharLenKey("résumé")), Some(&3));
+    assert_eq!(hashmap.get(&CharLenKey("resume")), None);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn path_keys() {
+    use std::path::Path;
+
+    use super::OsKey;
+
+    let paths = ["Cargo.toml", "src/lib.rs", "README.md"].map(|path| OsKey(Path::new(path)));
+
+    let mut builder = VfhmBuilder::default();
+    builder
+      .set_entries(paths.iter().copied().zip(1..).collect())
+      .find_params(1000);
+
+    let hashmap = builder.build_populated();
+
+    assert_eq!(hashmap.get(&OsKey(Path::new("src/lib.rs"))), Some(&2));
+    assert_eq!(hashmap.get(&OsKey(Path::new("README.md"))), Some(&3));
+    assert_eq!(hashmap.get(&OsKey(Path::new("src/main.rs"))), None);
+  }
+}
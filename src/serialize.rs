@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Vfhm, VfhmKey, VfhmParams};
+
+#[derive(Serialize)]
+#[serde(rename = "Vfhm")]
+struct VfhmRef<'a, K, V> {
+  params: VfhmParams,
+  entries: Vec<(&'a K, &'a V)>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Vfhm")]
+struct VfhmOwned<K, V> {
+  params: VfhmParams,
+  entries: Vec<(K, V)>,
+}
+
+impl<K, V> Serialize for Vfhm<K, V>
+where
+  K: Serialize,
+  V: Serialize,
+{
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    VfhmRef {
+      params: self.params,
+      entries: self.iter().collect(),
+    }
+    .serialize(serializer)
+  }
+}
+
+impl<'de, K, V> Deserialize<'de> for Vfhm<K, V>
+where
+  K: VfhmKey + Deserialize<'de>,
+  V: Deserialize<'de>,
+{
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let VfhmOwned { params, entries } = VfhmOwned::deserialize(deserializer)?;
+
+    if !params.is_valid() {
+      return Err(D::Error::custom(format_args!("invalid params {params:?}")));
+    }
+
+    let mut map = Vfhm::with_params(params);
+
+    for (key, value) in entries {
+      if !params.bound_check(&key) {
+        return Err(D::Error::custom("entry is outside of the params bounds"));
+      }
+
+      if map.try_insert(key, value).is_err() {
+        return Err(D::Error::custom("entries collide under the params"));
+      }
+    }
+
+    Ok(map)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Vfhm, VfhmParams};
+
+  #[test]
+  fn round_trip() {
+    let mut hashmap = Vfhm::with_params(VfhmParams(1, 112, 4, (6, 9)));
+
+    hashmap.insert("sunday".to_string(), 1);
+    hashmap.insert("monday".to_string(), 2);
+    hashmap.insert("tuesday".to_string(), 3);
+
+    let json = serde_json::to_string(&hashmap).unwrap();
+    let restored: Vfhm<String, i32> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored.len(), 3);
+    assert_eq!(restored.get("sunday"), Some(&1));
+    assert_eq!(restored.get("monday"), Some(&2));
+    assert_eq!(restored.get("tuesday"), Some(&3));
+    assert_eq!(restored.get("firday"), None);
+  }
+
+  #[test]
+  fn rejects_invalid_params() {
+    let result =
+      serde_json::from_str::<Vfhm<String, i32>>(r#"{"params":[0,1,100,[0,10]],"entries":[]}"#);
+    assert!(result.is_err());
+
+    let result =
+      serde_json::from_str::<Vfhm<String, i32>>(r#"{"params":[0,1,0,[9,6]],"entries":[]}"#);
+    assert!(result.is_err());
+
+    // Would ask for a table of 2^62 slots.
+    let result = serde_json::from_str::<Vfhm<String, i32>>(
+      r#"{"params":[0,4611686018427387903,0,[0,10]],"entries":[]}"#,
+    );
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn rejects_out_of_bounds_entries() {
+    let json = r#"{"params":[0,7,0,[6,9]],"entries":[["sunday",1],["a",2]]}"#;
+
+    assert!(serde_json::from_str::<Vfhm<String, i32>>(json).is_err());
+  }
+
+  #[test]
+  fn rejects_colliding_entries() {
+    // A single slot, so the second key can only collide.
+    let json = r#"{"params":[0,0,0,[0,10]],"entries":[["a",1],["b",2]]}"#;
+
+    assert!(serde_json::from_str::<Vfhm<String, i32>>(json).is_err());
+  }
+
+  #[test]
+  fn default_round_trip() {
+    let json = serde_json::to_string(&Vfhm::<String, i32>::default()).unwrap();
+    let restored: Vfhm<String, i32> = serde_json::from_str(&json).unwrap();
+
+    assert!(restored.is_empty());
+  }
+}
@@ -0,0 +1,66 @@
+use core::borrow::Borrow;
+
+use crate::{Keys, Vfhm, VfhmKey, VfhmParams};
+
+#[derive(Debug, Clone)]
+pub struct VfhmSet<K>(Vfhm<K, ()>);
+
+impl<K> VfhmSet<K> {
+  pub fn with_params<P>(maybe_params: P) -> Self
+  where
+    P: Into<VfhmParams>,
+  {
+    VfhmSet(Vfhm::with_params(maybe_params))
+  }
+
+  pub fn iter(&self) -> Keys<'_, K, ()> {
+    self.0.keys()
+  }
+}
+
+impl<K> VfhmSet<K>
+where
+  K: VfhmKey,
+{
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn contains<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.0.contains_key(key)
+  }
+
+  /// Returns `true` when the key wasn't in the set, a colliding key is overwritten like in
+  /// [`Vfhm::insert`].
+  pub fn insert(&mut self, key: K) -> bool {
+    let present = self.0.contains_key(&key);
+    self.0.insert(key, ());
+
+    !present
+  }
+
+  pub fn remove<Q>(&mut self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.0.remove(key).is_some()
+  }
+}
+
+impl<'a, K> IntoIterator for &'a VfhmSet<K> {
+  type Item = &'a K;
+  type IntoIter = Keys<'a, K, ()>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
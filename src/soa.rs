@@ -0,0 +1,154 @@
+use alloc::{vec, vec::Vec};
+use core::{borrow::Borrow, mem};
+
+use crate::{VfhmKey, VfhmParams};
+
+#[derive(Debug, Clone)]
+pub struct SoaVfhm<K, V> {
+  keys: Vec<K>,
+  values: Vec<V>,
+  occupied: Vec<u64>,
+  params: VfhmParams,
+  length: usize,
+}
+
+impl<K, V> SoaVfhm<K, V>
+where
+  K: Default,
+  V: Default,
+{
+  pub fn with_params<P>(maybe_params: P) -> Self
+  where
+    P: Into<VfhmParams>,
+  {
+    let params = maybe_params.into();
+    let size = params.mask_size();
+
+    SoaVfhm {
+      keys: (0..size).map(|_| K::default()).collect(),
+      values: (0..size).map(|_| V::default()).collect(),
+      occupied: vec![0; size.div_ceil(64)],
+      params,
+      length: 0,
+    }
+  }
+}
+
+impl<K, V> SoaVfhm<K, V>
+where
+  K: VfhmKey + Default,
+  V: Default,
+{
+  pub fn len(&self) -> usize {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  #[inline]
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    let index = key.table_key(self.params);
+    let hit = self.is_occupied(index) & key.table_key_compare(self.keys[index].borrow());
+
+    hit.then(|| &self.values[index])
+  }
+
+  pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+    let index = key.table_key(self.params);
+
+    let key = mem::replace(&mut self.keys[index], key);
+    let value = mem::replace(&mut self.values[index], value);
+
+    if self.is_occupied(index) {
+      Some((key, value))
+    } else {
+      self.occupied[index / 64] |= 1 << (index % 64);
+      self.length += 1;
+
+      None
+    }
+  }
+
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.remove_entry(key).map(|(_, value)| value)
+  }
+
+  /// Like [`SoaVfhm::remove`] but also hands back the stored key.
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    if !self.params.bound_check(key) {
+      return None;
+    }
+
+    let index = key.table_key(self.params);
+
+    if !(self.is_occupied(index) && key.table_key_compare(self.keys[index].borrow())) {
+      return None;
+    }
+
+    self.occupied[index / 64] &= !(1 << (index % 64));
+    self.length -= 1;
+
+    Some((
+      mem::take(&mut self.keys[index]),
+      mem::take(&mut self.values[index]),
+    ))
+  }
+
+  #[inline]
+  fn is_occupied(&self, index: usize) -> bool {
+    self.occupied[index / 64] & (1 << (index % 64)) != 0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::SoaVfhm;
+  use crate::VfhmParams;
+
+  #[test]
+  fn soa() {
+    let mut hashmap = SoaVfhm::with_params(VfhmParams(1, 112, 4, (6, 9)));
+
+    hashmap.insert("sunday", 1);
+    hashmap.insert("monday", 2);
+    hashmap.insert("tuesday", 3);
+
+    assert_eq!(hashmap.len(), 3);
+    assert_eq!(hashmap.get("sunday"), Some(&1));
+    assert_eq!(hashmap.get("monday"), Some(&2));
+    assert_eq!(hashmap.get("tuesday"), Some(&3));
+    assert_eq!(hashmap.get("firday"), None);
+
+    assert_eq!(hashmap.insert("monday", 4), Some(("monday", 2)));
+    assert_eq!(hashmap.remove_entry("sunday"), Some(("sunday", 1)));
+    assert_eq!(hashmap.remove("sunday"), None);
+    assert_eq!(hashmap.get("monday"), Some(&4));
+    assert_eq!(hashmap.len(), 2);
+  }
+}
@@ -1,9 +1,6 @@
-use std::{
-  marker::PhantomData,
-  ops::{Deref, DerefMut},
-};
+use core::{borrow::Borrow, marker::PhantomData, ops::Deref};
 
-use crate::{Vfhm, VfhmParams};
+use crate::{Drain, Entry, IterMut, ValuesMut, Vfhm, VfhmKey, VfhmParams};
 
 pub trait VfhmStaticMap {
   const SEED: usize;
@@ -11,8 +8,57 @@ pub trait VfhmStaticMap {
   const MASK_OFFSET: usize;
   const BONDS: (usize, usize);
 
-  fn into_params() -> VfhmParams {
+  /// Usable in const contexts, e.g. `[Option<(K, V)>; S::PARAMS.mask_size()]`. Fails to compile
+  /// for consts that can't match any key, inverted bounds or a mask offset past the bit width.
+  ///
+  /// Only const contexts fail under `cargo check`. Through generic code like
+  /// [`StaticVfhm::new`] the consts are evaluated after monomorphization, so bad consts are only
+  /// reported by `cargo build`.
+  ///
+  /// ```compile_fail
+  /// use vfhm::r#static::VfhmStaticMap;
+  ///
+  /// struct Inverted;
+  ///
+  /// impl VfhmStaticMap for Inverted {
+  ///   const SEED: usize = 1;
+  ///   const MASK: usize = 112;
+  ///   const MASK_OFFSET: usize = 4;
+  ///   const BONDS: (usize, usize) = (9, 6);
+  /// }
+  ///
+  /// const SLOTS: usize = Inverted::PARAMS.mask_size();
+  /// ```
+  ///
+  /// ```compile_fail
+  /// use vfhm::r#static::VfhmStaticMap;
+  ///
+  /// struct Shifted;
+  ///
+  /// impl VfhmStaticMap for Shifted {
+  ///   const SEED: usize = 1;
+  ///   const MASK: usize = 112;
+  ///   const MASK_OFFSET: usize = usize::BITS as usize;
+  ///   const BONDS: (usize, usize) = (6, 9);
+  /// }
+  ///
+  /// const SLOTS: usize = Shifted::PARAMS.mask_size();
+  /// ```
+  const PARAMS: VfhmParams = {
+    assert!(
+      Self::BONDS.0 <= Self::BONDS.1,
+      "BONDS lower bound is above the upper bound"
+    );
+    assert!(
+      Self::MASK_OFFSET < usize::BITS as usize,
+      "MASK_OFFSET is past the bit width"
+    );
+
     VfhmParams(Self::SEED, Self::MASK, Self::MASK_OFFSET, Self::BONDS)
+  };
+
+  fn into_params() -> VfhmParams {
+    Self::PARAMS
   }
 }
 
@@ -25,6 +71,154 @@ where
   pub fn new() -> Self {
     StaticVfhm(Vfhm::with_params(S::into_params()), PhantomData::<S>)
   }
+
+  /// Wraps `map` when its params are exactly `S`'s, otherwise hands it back.
+  pub fn try_from_vfhm(map: Vfhm<K, V>) -> Result<Self, Vfhm<K, V>> {
+    if map.params == S::into_params() {
+      Ok(StaticVfhm(map, PhantomData::<S>))
+    } else {
+      Err(map)
+    }
+  }
+}
+
+/// Only the mutations that keep the params are forwarded, there's no `DerefMut` since calls like
+/// [`Vfhm::recompute_bounds`] would leave the map out of step with `S`.
+///
+/// ```compile_fail
+/// use vfhm::r#static::{StaticVfhm, VfhmStaticMap};
+///
+/// struct Days;
+///
+/// impl VfhmStaticMap for Days {
+///   const SEED: usize = 1;
+///   const MASK: usize = 112;
+///   const MASK_OFFSET: usize = 4;
+///   const BONDS: (usize, usize) = (6, 9);
+/// }
+///
+/// let mut hashmap = StaticVfhm::<&str, i32, Days>::new();
+/// hashmap.insert("sunday", 1);
+/// hashmap.recompute_bounds();
+/// ```
+impl<K, V, S> StaticVfhm<K, V, S> {
+  pub fn into_inner(self) -> Vfhm<K, V> {
+    self.0
+  }
+
+  pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+    self.0.iter_mut()
+  }
+
+  pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+    self.0.values_mut()
+  }
+
+  pub fn clear(&mut self) {
+    self.0.clear();
+  }
+
+  pub fn drain(&mut self) -> Drain<'_, K, V> {
+    self.0.drain()
+  }
+
+  pub fn retain<F>(&mut self, f: F)
+  where
+    F: FnMut(&K, &mut V) -> bool,
+  {
+    self.0.retain(f);
+  }
+}
+
+impl<K, V, S> StaticVfhm<K, V, S>
+where
+  K: VfhmKey,
+{
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.0.get_mut(key)
+  }
+
+  pub fn get_disjoint_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.0.get_disjoint_mut(keys)
+  }
+
+  pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+    self.0.insert(key, value)
+  }
+
+  pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<(K, V)>, (K, V)> {
+    self.0.try_insert(key, value)
+  }
+
+  pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+    self.0.entry(key)
+  }
+
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.0.remove(key)
+  }
+
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Borrow<Q>,
+    Q: VfhmKey + ?Sized,
+  {
+    self.0.remove_entry(key)
+  }
+}
+
+impl<K, V, S> StaticVfhm<K, V, S>
+where
+  K: VfhmKey,
+  S: VfhmStaticMap,
+{
+  /// Panics if a key is outside `S::BONDS` or collides with another key, the consts don't separate
+  /// the intended keys then.
+  pub fn with_entries<I>(entries: I) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+  {
+    let mut map = Self::new();
+
+    for (key, value) in entries {
+      assert!(
+        map.0.params.bound_check(&key),
+        "key is outside of the static map bounds"
+      );
+
+      let inserted = map.0.try_insert(key, value);
+
+      assert!(
+        inserted.is_ok(),
+        "key collides with another key under the static map params"
+      );
+    }
+
+    map
+  }
+}
+
+impl<K, S> StaticVfhm<K, usize, S>
+where
+  K: VfhmKey + Clone,
+  S: VfhmStaticMap,
+{
+  /// Maps every key to its position in `keys`, checked like [`StaticVfhm::with_entries`].
+  pub fn from_keys(keys: &[K]) -> Self {
+    Self::with_entries(keys.iter().cloned().zip(0..))
+  }
 }
 
 impl<K, V, S> Default for StaticVfhm<K, V, S>
@@ -44,9 +238,15 @@ impl<K, V, S> Deref for StaticVfhm<K, V, S> {
   }
 }
 
-impl<K, V, S> DerefMut for StaticVfhm<K, V, S> {
-  fn deref_mut(&mut self) -> &mut Self::Target {
-    &mut self.0
+impl<K, V, S> Extend<(K, V)> for StaticVfhm<K, V, S>
+where
+  K: VfhmKey,
+{
+  fn extend<I>(&mut self, iter: I)
+  where
+    I: IntoIterator<Item = (K, V)>,
+  {
+    self.0.extend(iter);
   }
 }
 
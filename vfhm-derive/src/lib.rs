@@ -0,0 +1,80 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Error, Expr};
+
+mod perfect_map;
+
+use crate::perfect_map::{expand_perfect_map, PerfectMap};
+
+#[proc_macro]
+pub fn perfect_map(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as PerfectMap);
+
+  expand_perfect_map(input)
+    .unwrap_or_else(Error::into_compile_error)
+    .into()
+}
+
+#[proc_macro_derive(VfhmStaticMap, attributes(vfhm))]
+pub fn derive_static_map(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+
+  expand_static_map(input)
+    .unwrap_or_else(Error::into_compile_error)
+    .into()
+}
+
+fn expand_static_map(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+  let mut seed = None;
+  let mut mask = None;
+  let mut mask_offset = None;
+  let mut bounds = None;
+
+  for attr in input
+    .attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("vfhm"))
+  {
+    attr.parse_nested_meta(|meta| {
+      let slot = if meta.path.is_ident("seed") {
+        &mut seed
+      } else if meta.path.is_ident("mask") {
+        &mut mask
+      } else if meta.path.is_ident("mask_offset") {
+        &mut mask_offset
+      } else if meta.path.is_ident("bounds") {
+        &mut bounds
+      } else {
+        return Err(meta.error("expected `seed`, `mask`, `mask_offset` or `bounds`"));
+      };
+
+      *slot = Some(meta.value()?.parse::<Expr>()?);
+
+      Ok(())
+    })?;
+  }
+
+  let missing = |name: &str| {
+    Error::new_spanned(
+      &input.ident,
+      format!("missing `#[vfhm({name} = ...)]` attribute"),
+    )
+  };
+
+  let seed = seed.ok_or_else(|| missing("seed"))?;
+  let mask = mask.ok_or_else(|| missing("mask"))?;
+  let mask_offset = mask_offset.ok_or_else(|| missing("mask_offset"))?;
+  let bounds = bounds.ok_or_else(|| missing("bounds"))?;
+
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  Ok(quote! {
+    impl #impl_generics ::vfhm::r#static::VfhmStaticMap for #ident #ty_generics #where_clause {
+      const SEED: usize = #seed;
+      const MASK: usize = #mask;
+      const MASK_OFFSET: usize = #mask_offset;
+      const BONDS: (usize, usize) = #bounds;
+    }
+  })
+}
@@ -0,0 +1,219 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use syn::{
+  braced,
+  parse::{Parse, ParseStream},
+  punctuated::Punctuated,
+  Error, Expr, Ident, Lit, Token, Type, Visibility,
+};
+
+const MAX_ITERATIONS: usize = 1_000_000;
+
+const POINTER_WIDTHS: [u32; 2] = [32, 64];
+
+pub struct PerfectMap {
+  vis: Visibility,
+  ident: Ident,
+  key: Type,
+  value: Type,
+  entries: Punctuated<Entry, Token![,]>,
+}
+
+struct Entry {
+  key: Lit,
+  value: Expr,
+}
+
+impl Parse for PerfectMap {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let vis = input.parse()?;
+    let ident = input.parse()?;
+
+    input.parse::<Token![<]>()?;
+    let key = input.parse()?;
+    input.parse::<Token![,]>()?;
+    let value = input.parse()?;
+    input.parse::<Token![>]>()?;
+
+    let content;
+    braced!(content in input);
+
+    Ok(PerfectMap {
+      vis,
+      ident,
+      key,
+      value,
+      entries: content.parse_terminated(Entry::parse, Token![,])?,
+    })
+  }
+}
+
+impl Parse for Entry {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let key = input.parse()?;
+    input.parse::<Token![=>]>()?;
+    let value = input.parse()?;
+
+    Ok(Entry { key, value })
+  }
+}
+
+pub fn expand_perfect_map(map: PerfectMap) -> syn::Result<TokenStream> {
+  let keys = map
+    .entries
+    .iter()
+    .map(|entry| match &entry.key {
+      Lit::Str(lit) => Ok(lit.value().into_bytes()),
+      Lit::ByteStr(lit) => Ok(lit.value()),
+      lit => Err(Error::new_spanned(
+        lit,
+        "expected a string or byte string literal",
+      )),
+    })
+    .collect::<syn::Result<Vec<_>>>()?;
+
+  let PerfectMap {
+    vis,
+    ident,
+    key,
+    value,
+    entries,
+  } = &map;
+  let entry_keys = entries.iter().map(|entry| &entry.key);
+  let entry_values = entries.iter().map(|entry| &entry.value);
+
+  // The hash wraps at the target's `usize`, which the macro can't see, so every supported width
+  // gets its own params.
+  let impls = POINTER_WIDTHS
+    .iter()
+    .map(|&bits| {
+      let (seed, mask, mask_offset, (lower, upper)) =
+        find_params(&keys, bits).ok_or_else(|| {
+          Error::new_spanned(
+            ident,
+            format!("no conflictless {bits} bit params found in {MAX_ITERATIONS} iterations"),
+          )
+        })?;
+      let width = bits.to_string();
+      let (seed, mask, mask_offset) = (
+        Literal::u64_unsuffixed(seed),
+        Literal::u64_unsuffixed(mask),
+        Literal::u64_unsuffixed(mask_offset),
+      );
+
+      Ok(quote! {
+        #[cfg(target_pointer_width = #width)]
+        impl ::vfhm::r#static::VfhmStaticMap for #ident {
+          const SEED: usize = #seed;
+          const MASK: usize = #mask;
+          const MASK_OFFSET: usize = #mask_offset;
+          const BONDS: (usize, usize) = (#lower, #upper);
+        }
+      })
+    })
+    .collect::<syn::Result<Vec<_>>>()?;
+  let widths = POINTER_WIDTHS.iter().map(u32::to_string);
+
+  Ok(quote! {
+    #vis struct #ident;
+
+    #(#impls)*
+
+    #[cfg(not(any(#(target_pointer_width = #widths),*)))]
+    compile_error!("perfect_map! only supports 32 and 64 bit targets");
+
+    impl #ident {
+      #[allow(dead_code)]
+      #vis fn new() -> ::vfhm::r#static::StaticVfhm<#key, #value, #ident> {
+        let mut map: ::vfhm::r#static::StaticVfhm<#key, #value, #ident> =
+          ::vfhm::r#static::StaticVfhm::new();
+
+        #(map.insert(#entry_keys, #entry_values);)*
+
+        map
+      }
+    }
+  })
+}
+
+// Mirrors `VfhmBuilder::try_find_params` and `VfhmKey::table_key` so the search can run while
+// expanding the macro. Wrapping `u64` arithmetic agrees with a `bits` wide `usize` on the low
+// `bits` bits, so params whose mask fits in them hash the same on the target.
+fn find_params(keys: &[Vec<u8>], bits: u32) -> Option<(u64, u64, u64, (usize, usize))> {
+  let lower = keys.iter().map(Vec::len).min().unwrap_or(0);
+  let upper = keys.iter().map(Vec::len).max().unwrap_or(0);
+
+  let (mut seed, mut mask, mut mask_offset) = (0u64, 1u64, 0u64);
+
+  for _ in 0..MAX_ITERATIONS {
+    if mask > u64::MAX >> (u64::BITS - bits) {
+      return None;
+    }
+
+    let mut occupied = vec![false; (mask >> mask_offset) as usize + 1];
+
+    if keys.iter().all(|key| {
+      let mut index: u64 = 1;
+
+      for byte in key {
+        index = index.wrapping_mul(*byte as u64).wrapping_sub(seed)
+      }
+
+      !std::mem::replace(
+        &mut occupied[((index & mask) >> mask_offset) as usize],
+        true,
+      )
+    }) {
+      return Some((seed, mask, mask_offset, (lower, upper)));
+    }
+
+    if seed == mask >> mask_offset {
+      seed = 0;
+
+      if mask_offset + (1 + (mask >> mask_offset)).isqrt() + 1 < bits as u64 {
+        mask <<= 1;
+        mask_offset += 1;
+      } else if mask_offset > 0 {
+        mask = (mask >> (mask_offset - 1)) | 1;
+        mask_offset = 0;
+      } else {
+        return None;
+      }
+    } else {
+      seed += 1;
+    }
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::find_params;
+
+  #[test]
+  fn params_fit_32_bits() {
+    let keys: Vec<Vec<u8>> = ["sunday", "monday", "tuesday", "wednesday", "thursday"]
+      .iter()
+      .map(|key| key.as_bytes().to_vec())
+      .collect();
+
+    let (seed, mask, mask_offset, _) = find_params(&keys, 32).unwrap();
+    let (seed, mask) = (u32::try_from(seed).unwrap(), u32::try_from(mask).unwrap());
+
+    let mut slots: Vec<u32> = keys
+      .iter()
+      .map(|key| {
+        let index = key.iter().fold(1u32, |index, byte| {
+          index.wrapping_mul(*byte as u32).wrapping_sub(seed)
+        });
+
+        (index & mask) >> mask_offset
+      })
+      .collect();
+    slots.sort_unstable();
+    slots.dedup();
+
+    assert_eq!(slots.len(), keys.len());
+  }
+}